@@ -0,0 +1,117 @@
+// Allocation tracking for solution runs, turned on with `--features alloc-stats`
+//
+// The counting allocator keeps its counters per thread, and every star runs start to finish on one thread,
+//      so runs on other threads (like a timed out solver still going in the background) don't skew the numbers
+// Without the feature the system allocator is used as normal and `measure` never reports anything
+use std::cell::Cell;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes_allocated: u64,
+    pub peak_bytes: u64,
+}
+
+impl std::fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} allocs, {} allocated, {} peak", self.allocations, format_bytes(self.bytes_allocated), format_bytes(self.peak_bytes))
+    }
+}
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static BYTES_ALLOCATED: Cell<u64> = const { Cell::new(0) };
+
+    // Signed because memory allocated on one thread can be freed on another
+    static LIVE_BYTES: Cell<i64> = const { Cell::new(0) };
+    static PEAK_LIVE_BYTES: Cell<i64> = const { Cell::new(0) };
+}
+
+pub fn enabled () -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+// `try_with` because the allocator can still be called while a thread's locals are being torn down
+#[cfg_attr(not(feature = "alloc-stats"), allow(unused))]
+fn record (allocations: u64, allocated: u64, live_change: i64) {
+    let _ = ALLOCATIONS.try_with(| count | count.set(count.get() + allocations));
+    let _ = BYTES_ALLOCATED.try_with(| bytes | bytes.set(bytes.get() + allocated));
+    let _ = LIVE_BYTES.try_with(| live | {
+        let now = live.get() + live_change;
+        live.set(now);
+        let _ = PEAK_LIVE_BYTES.try_with(| peak | peak.set(peak.get().max(now)));
+    });
+}
+
+// Runs `run` and reports what it allocated on this thread, or nothing when the feature is off
+pub fn measure <T> (run: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (run(), None);
+    }
+
+    let start_allocations = ALLOCATIONS.with(Cell::get);
+    let start_bytes = BYTES_ALLOCATED.with(Cell::get);
+    let start_live = LIVE_BYTES.with(Cell::get);
+    PEAK_LIVE_BYTES.with(| peak | peak.set(start_live));
+
+    let result = run();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.with(Cell::get) - start_allocations,
+        bytes_allocated: BYTES_ALLOCATED.with(Cell::get) - start_bytes,
+        peak_bytes: (PEAK_LIVE_BYTES.with(Cell::get) - start_live).max(0) as u64,
+    };
+    (result, Some(stats))
+}
+
+pub fn format_bytes (bytes: u64) -> String {
+    let units = [ "B", "KiB", "MiB", "GiB" ];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    }
+    else {
+        format!("{:.1} {}", size, units[unit])
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use super::record;
+
+    struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc (&self, layout: Layout) -> *mut u8 {
+            record(1, layout.size() as u64, layout.size() as i64);
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn alloc_zeroed (&self, layout: Layout) -> *mut u8 {
+            record(1, layout.size() as u64, layout.size() as i64);
+            unsafe { System.alloc_zeroed(layout) }
+        }
+
+        unsafe fn dealloc (&self, ptr: *mut u8, layout: Layout) {
+            record(0, 0, -(layout.size() as i64));
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        // Counted as one allocation of however much the block grew by
+        unsafe fn realloc (&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let growth = new_size.saturating_sub(layout.size()) as u64;
+            record(1, growth, new_size as i64 - layout.size() as i64);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+}
@@ -0,0 +1,209 @@
+use std::{convert::Infallible, str::FromStr};
+use serde::{Deserialize, Serialize, Serializer, ser::SerializeSeq};
+
+// What a star returns, so numbers stay numbers instead of being turned into strings and re-parsed by whoever
+//      wants to compare them
+// Most puzzles have a number for an answer, some spell a word out or draw it on a grid of characters
+//
+// Integers are equal no matter which variant holds them, so `Answer::from(7u8) == Answer::from(7i64)`, and
+//      text equals a grid that renders the same
+// Serialized with its variant, like {"unsigned":7}, so the --cache gets back exactly what it stored (serde_json
+//      only reads integers past u64 exactly when it's told to expect one)
+// JSON output wants the plain value instead, see `plain`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Answer {
+    Unsigned(u128),
+
+    // Only negative numbers end up here when built with `From`, anything else fits in `Unsigned`
+    Signed(i128),
+    Text(String),
+
+    // One string per row
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    // Splits multi-line text into a grid, a single line stays text
+    pub fn grid (text: &str) -> Self {
+        if text.contains('\n') {
+            Answer::Grid(text.lines().map(String::from).collect())
+        }
+        else {
+            Answer::Text(text.to_string())
+        }
+    }
+
+    // Serializes as a JSON number (even u128s, which JavaScript can't read exactly), text as a string, and
+    //      grids as an array of their rows
+    pub fn plain (&self) -> Plain<'_> {
+        Plain(self)
+    }
+
+    // Short machine-readable name for the kind of answer, used by JSON and CSV records
+    pub fn kind (&self) -> &'static str {
+        match self {
+            Answer::Unsigned(_) | Answer::Signed(_) => "integer",
+            Answer::Text(_) => "text",
+            Answer::Grid(_) => "grid",
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Signed(b)) | (Answer::Signed(b), Answer::Unsigned(a)) => u128::try_from(*b).is_ok_and(| b | *a == b),
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Text(_) | Answer::Grid(_), Answer::Text(_) | Answer::Grid(_)) => self.to_string() == other.to_string(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+// The reverse of Display, for answers that were written down as text (like in answers.toml)
+// Anything that reads as a number is one, and anything spread over more than one line is a grid
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str (text: &str) -> Result<Self, Self::Err> {
+        if let Ok(value) = text.parse::<u128>() {
+            return Ok(Answer::Unsigned(value));
+        }
+        if let Ok(value) = text.parse::<i128>() {
+            return Ok(Answer::Signed(value));
+        }
+        Ok(Answer::grid(text))
+    }
+}
+
+pub struct Plain<'a>(&'a Answer);
+
+impl Serialize for Plain<'_> {
+    fn serialize <S: Serializer> (&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Answer::Unsigned(value) => serializer.serialize_u128(*value),
+            Answer::Signed(value) => serializer.serialize_i128(*value),
+            Answer::Text(text) => serializer.serialize_str(text),
+            Answer::Grid(rows) => {
+                let mut seq = serializer.serialize_seq(Some(rows.len()))?;
+                for row in rows {
+                    seq.serialize_element(row)?;
+                }
+                seq.end()
+            }
+        }
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(value: $ty) -> Self {
+                    Answer::Unsigned(value as u128)
+                }
+            }
+        )*
+    };
+}
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! from_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(value: $ty) -> Self {
+                    if value < 0 {
+                        Answer::Signed(value as i128)
+                    }
+                    else {
+                        Answer::Unsigned(value as u128)
+                    }
+                }
+            }
+        )*
+    };
+}
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+// Like `Answer::grid`, multi-line text is a grid
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        if text.contains('\n') {
+            Answer::grid(&text)
+        }
+        else {
+            Answer::Text(text)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::grid(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_equal_across_variants () {
+        assert_eq!(Answer::Signed(7), Answer::Unsigned(7));
+        assert_eq!(Answer::Unsigned(7), Answer::Signed(7));
+        assert_eq!(Answer::from(7u8), Answer::from(7i64));
+        assert_ne!(Answer::Signed(-7), Answer::Unsigned(7));
+        assert_ne!(Answer::Unsigned(u128::MAX), Answer::Signed(-1));
+        assert_ne!(Answer::Unsigned(7), Answer::from("7"));
+    }
+
+    #[test]
+    fn text_equals_grid_with_the_same_rendering () {
+        assert_eq!(Answer::Text(String::from("ab\ncd")), Answer::Grid(vec![ String::from("ab"), String::from("cd") ]));
+        assert_eq!(Answer::from("ab\ncd"), Answer::grid("ab\ncd"));
+        assert_eq!(Answer::from(String::from("ab\ncd")).kind(), "grid");
+        assert_eq!(Answer::from("abcd").kind(), "text");
+    }
+
+    #[test]
+    fn from_str () {
+        let parse = | text: &str | -> Answer {
+            let Ok(answer) = text.parse::<Answer>();
+            answer
+        };
+        assert!(matches!(parse("42"), Answer::Unsigned(42)));
+        assert!(matches!(parse("-42"), Answer::Signed(-42)));
+        assert!(matches!(parse("340282366920938463463374607431768211455"), Answer::Unsigned(u128::MAX)));
+        assert!(matches!(parse("EFGH"), Answer::Text(text) if text == "EFGH"));
+        assert!(matches!(parse("#.\n.#"), Answer::Grid(rows) if rows == [ "#.", ".#" ]));
+    }
+
+    #[test]
+    fn round_trips_through_json () {
+        for answer in [ Answer::Unsigned(u128::MAX), Answer::Signed(i128::MIN), Answer::from("EFGH"), Answer::grid("#.\n.#") ] {
+            let json = serde_json::to_string(&answer).unwrap();
+            let back: Answer = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.kind(), answer.kind());
+            assert_eq!(back, answer);
+        }
+        assert_eq!(serde_json::to_string(&Answer::Unsigned(u128::MAX).plain()).unwrap(), "340282366920938463463374607431768211455");
+        assert_eq!(serde_json::to_string(&Answer::grid("#.\n.#").plain()).unwrap(), r##"["#.",".#"]"##);
+    }
+}
@@ -0,0 +1,62 @@
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use crate::answer::Answer;
+use crate::files;
+use crate::runner::DataSet;
+use crate::solution::Part;
+
+// Expected answers for a day live next to its data in <data_folder_root>/<day>/answers.toml:
+//
+//      [example]
+//      star_one = 3
+//      star_two = "6"
+//
+//      [full]
+//      star_one = "1227775554"
+//
+// Answers can be integers or strings (answers that overflow a TOML integer, like u128s, need to be strings)
+// Either way they're compared as an `Answer`, so `star_two = "6"` still matches a star that returns 6
+// Any star or data set without an answer is just skipped when checking
+#[derive(Debug, Default, Deserialize)]
+pub struct Answers {
+    example: Option<StarAnswers>,
+    full: Option<StarAnswers>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct StarAnswers {
+    star_one: Option<toml::Value>,
+    star_two: Option<toml::Value>,
+}
+
+pub fn answers_path (data_folder_root: &Path, day: u8) -> PathBuf {
+    data_folder_root.join(day.to_string()).join("answers.toml")
+}
+
+impl Answers {
+    pub fn load (data_folder_root: &Path, day: u8) -> Result<Self, String> {
+        let path = answers_path(data_folder_root, day);
+        let Some(contents) = files::read_optional(&path)? else {
+            return Ok(Answers::default());
+        };
+        toml::from_str(&contents).map_err(| err | format!("Could not parse '{}': {}", path.display(), err))
+    }
+
+    pub fn expected (&self, data_set: DataSet, part: Part) -> Option<Answer> {
+        let stars = match data_set {
+            DataSet::Example => self.example.as_ref(),
+            DataSet::Full => self.full.as_ref(),
+        }?;
+        let answer = match part {
+            Part::One => stars.star_one.as_ref(),
+            Part::Two => stars.star_two.as_ref(),
+        }?;
+
+        let Ok(answer) = match answer {
+            toml::Value::Integer(value) => Ok(Answer::from(*value)),
+            toml::Value::String(text) => text.parse::<Answer>(),
+            other => other.to_string().parse::<Answer>(),
+        };
+        Some(answer)
+    }
+}
@@ -0,0 +1,192 @@
+use std::path::Path;
+use std::time::Duration;
+use owo_colors::Style;
+use crate::alloc::AllocStats;
+use crate::history::HistoryEntry;
+use crate::input::Input;
+use crate::output::{self, Record};
+use crate::params::Params;
+use crate::phase::PhaseTime;
+use crate::pool;
+use crate::runner::{self, DataSet, Outcome, format_duration};
+use crate::solution::{self, Part, Solution};
+
+// Batches of stars spread over a thread pool: --all runs both stars of every day, --inputs one star over a
+//      folder of alternative inputs
+// Inputs are read up front so the jobs can share them, and results come back in the order the jobs were made
+
+// A day's input for a batch, None for days that aren't implemented (so there's no point reading it)
+pub type BatchInput = Option<Result<Input, String>>;
+
+// One star to run in a batch, with the input already read (or why it couldn't be)
+pub struct BatchJob <'a> {
+    pub solution: &'static dyn Solution,
+    pub part: Part,
+    pub params: Params,
+    pub input: Option<&'a Result<Input, String>>,
+}
+
+// A `runner::RunResult`, without an elapsed time for stars that never ran
+pub type BatchResult = (Outcome, Option<Duration>, Option<AllocStats>, Vec<PhaseTime>);
+
+// Runs every job on up to `threads` threads, results come back in the same order as `jobs`
+// Panics are reported in the results, keep them off stderr with `runner::quiet_panics` while this runs
+pub fn run_batch (jobs: Vec<BatchJob>, threads: usize, timeout: Option<Duration>) -> Vec<BatchResult> {
+    pool::map(jobs, threads, | job | match job.input {
+        None => (Outcome::NotImplemented, None, None, vec![]),
+        Some(Err(err)) => (Outcome::MissingInput(err.clone()), None, None, vec![]),
+        Some(Ok(input)) => {
+            let (outcome, elapsed, alloc_stats, phases) = runner::run_with_timeout(job.solution, job.part, input, &job.params, timeout);
+            (outcome, Some(elapsed), alloc_stats, phases)
+        }
+    })
+}
+
+// One star of a batch and how its run went
+pub struct BatchRun {
+    pub day: u8,
+    pub part: Part,
+    pub implementation: &'static str,
+
+    // The data set, or the file name for --inputs
+    pub input: String,
+    pub outcome: Outcome,
+    pub elapsed: Option<Duration>,
+    pub alloc_stats: Option<AllocStats>,
+    pub phases: Vec<PhaseTime>,
+}
+
+pub struct BatchReport {
+    // Elapsed times are wall-clock, so anything over 1 means they include contention
+    pub threads: usize,
+    pub runs: Vec<BatchRun>,
+
+    // Timings of every star that was solved, for the history file
+    pub history: Vec<HistoryEntry>,
+}
+
+impl BatchReport {
+    pub fn failures (&self) -> usize {
+        self.runs.iter().filter(| run | !matches!(run.outcome, Outcome::Solved(_))).count()
+    }
+
+    pub fn records (&self) -> Vec<Record> {
+        self.runs.iter().map(| run | Record {
+            jobs: self.threads,
+            ..Record::from(run.day, run.part, run.implementation, &run.input, &run.outcome, run.elapsed, run.alloc_stats).with_phases(run.elapsed.unwrap_or_default(), &run.phases)
+        }).collect()
+    }
+
+    // The --all table, one row per star
+    pub fn text (&self) -> String {
+        let rows = self.runs.iter().map(| run | [ vec![
+            run.day.to_string(),
+            run.part.to_string(),
+            run.implementation.to_string(),
+            run.input.clone(),
+            run.outcome.to_string(),
+            run.elapsed.map(format_duration).unwrap_or(String::from("-")),
+        ], output::memory_cell(run.alloc_stats) ].concat()).collect::<Vec<Vec<String>>>();
+
+        self.contention_note() + &output::table(&output::with_memory_column(vec![ "Day", "Part", "Impl", "Data", "Answer", "Elapsed" ]), &rows)
+    }
+
+    // The --inputs table, one row per input file of the same star
+    pub fn inputs_text (&self, dir: &Path) -> String {
+        let Some(first) = self.runs.first() else {
+            return String::new();
+        };
+        let rows = self.runs.iter().map(| run | [ vec![
+            run.input.clone(),
+            run.outcome.to_string(),
+            run.elapsed.map(format_duration).unwrap_or(String::from("-")),
+        ], output::memory_cell(run.alloc_stats) ].concat()).collect::<Vec<Vec<String>>>();
+
+        let mut text = format!("Day {} star {} ({}) over {}\n\n", first.day, first.part, first.implementation, dir.display());
+        text += &self.contention_note();
+        text += &output::table(&output::with_memory_column(vec![ "Input", "Answer", "Elapsed" ]), &rows);
+        if self.failures() > 0 {
+            text += &format!("\n\n{} {} of {} inputs didn't produce an answer", output::paint("FAIL", Style::new().red().bold()), self.failures(), self.runs.len());
+        }
+        text
+    }
+
+    fn contention_note (&self) -> String {
+        if self.threads > 1 {
+            format!("Ran on {} threads, so elapsed times are wall-clock under contention (use --serial for clean timings)\n\n", self.threads)
+        }
+        else {
+            String::new()
+        }
+    }
+}
+
+// Both stars of every day, with the default implementation, on each of `data_sets`
+pub fn run_all (data_folder_root: &Path, data_sets: &[DataSet], threads: usize, timeout: Option<Duration>) -> BatchReport {
+    let mut inputs: Vec<(&'static dyn Solution, DataSet, BatchInput)> = Vec::new();
+    for day in 1..=25 {
+        let Some(solution) = solution::for_day(day).first().copied() else {
+            continue;
+        };
+
+        for data_set in data_sets {
+            let input = if solution.implemented() {
+                Some(runner::read_input(&runner::data_path(data_folder_root, day, *data_set)).map_err(| err | err.to_string()))
+            }
+            else {
+                None
+            };
+            inputs.push((solution, *data_set, input));
+        }
+    }
+
+    let stars = inputs.iter()
+        .flat_map(| (solution, data_set, input) | [ Part::One, Part::Two ].map(| part | (*solution, part, *data_set, input.as_ref())))
+        .collect::<Vec<_>>();
+    let jobs = stars.iter()
+        .map(| (solution, part, data_set, input) | BatchJob { solution: *solution, part: *part, params: Params::default().for_data_set(Some(*data_set)), input: *input })
+        .collect::<Vec<BatchJob>>();
+
+    let _quiet = runner::quiet_panics();
+    let results = run_batch(jobs, threads, timeout);
+
+    let mut report = BatchReport { threads, runs: Vec::new(), history: Vec::new() };
+    for ((solution, part, data_set, input), (outcome, elapsed, alloc_stats, phases)) in stars.into_iter().zip(results) {
+        if let (Outcome::Solved(_), Some(Ok(input)), Some(elapsed)) = (&outcome, input, elapsed) {
+            report.history.push(HistoryEntry::new(solution.day(), part, solution.name(), input.raw(), elapsed, threads, "run"));
+        }
+        report.runs.push(BatchRun { day: solution.day(), part, implementation: solution.name(), input: data_set.to_string(), outcome, elapsed, alloc_stats, phases });
+    }
+    report
+}
+
+// One star over every .txt input in `dir`, Err when there aren't any to run
+// Alternative inputs could be anything, so they get the full puzzle's parameters unless they're named example.txt
+//      (see `Params::for_data_set`)
+pub fn run_inputs (dir: &Path, solution: &'static dyn Solution, part: Part, params: &Params, threads: usize, timeout: Option<Duration>) -> Result<BatchReport, String> {
+    let paths = runner::list_inputs(dir).map_err(| err | format!("Could not list the inputs in '{}': {}", dir.display(), err))?;
+    if paths.is_empty() {
+        return Err(format!("There are no .txt inputs in '{}'", dir.display()));
+    }
+
+    let inputs = paths.iter()
+        .map(| path | runner::read_input(path).map_err(| err | err.to_string()))
+        .collect::<Vec<Result<Input, String>>>();
+    let jobs = inputs.iter()
+        .zip(&paths)
+        .map(| (input, path) | BatchJob { solution, part, params: params.for_data_set(DataSet::from_path(path)), input: Some(input) })
+        .collect::<Vec<BatchJob>>();
+
+    let _quiet = runner::quiet_panics();
+    let results = run_batch(jobs, threads, timeout);
+
+    let mut report = BatchReport { threads, runs: Vec::new(), history: Vec::new() };
+    for ((path, input), (outcome, elapsed, alloc_stats, phases)) in paths.iter().zip(&inputs).zip(results) {
+        if let (Outcome::Solved(_), Ok(input), Some(elapsed)) = (&outcome, input, elapsed) {
+            report.history.push(HistoryEntry::new(solution.day(), part, solution.name(), input.raw(), elapsed, threads, "run"));
+        }
+        let file_name = path.file_name().map(| name | name.to_string_lossy().into_owned()).unwrap_or_default();
+        report.runs.push(BatchRun { day: solution.day(), part, implementation: solution.name(), input: file_name, outcome, elapsed, alloc_stats, phases });
+    }
+    Ok(report)
+}
@@ -0,0 +1,260 @@
+use std::path::Path;
+use std::time::Duration;
+use crate::alloc::AllocStats;
+use crate::answer::Answer;
+use crate::history::HistoryEntry;
+use crate::input::Input;
+use crate::output;
+use crate::params::Params;
+use crate::runner::{self, InputSource, Outcome, format_duration};
+use crate::solution::{self, Part, Solution};
+
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    pub fn from (samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "Stats need at least one sample");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let count = sorted.len();
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        }
+        else {
+            sorted[count / 2]
+        };
+
+        // Nearest-rank percentile
+        let p95_rank = (count as f64 * 0.95).ceil() as usize;
+        let p95 = sorted[p95_rank.max(1) - 1];
+
+        let nanos = sorted.iter().map(| sample | sample.as_nanos() as f64).collect::<Vec<f64>>();
+        let mean = nanos.iter().sum::<f64>() / count as f64;
+        let variance = nanos.iter().map(| sample | (sample - mean).powi(2)).sum::<f64>() / count as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            p95,
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+pub struct BenchResult {
+    pub answer: Answer,
+    pub samples: Vec<Duration>,
+
+    // From the last run, every run of a deterministic solution allocates the same
+    pub alloc_stats: Option<AllocStats>,
+}
+
+// Runs `warmup` untimed iterations, then `iterations` timed ones
+// Every iteration borrows the same input, nothing is copied per run
+// Bails out with the outcome of the first run that doesn't produce an answer (including one that times out)
+pub fn bench (solution: &'static dyn Solution, part: Part, input: &Input, params: &Params, warmup: usize, iterations: usize, timeout: Option<Duration>) -> Result<BenchResult, Outcome> {
+    let mut answer: Option<Answer> = None;
+    let mut samples: Vec<Duration> = Vec::with_capacity(iterations);
+    let mut alloc_stats: Option<AllocStats> = None;
+
+    for iteration in 0..(warmup + iterations) {
+        let (outcome, elapsed, run_alloc_stats, _) = runner::run_with_timeout(solution, part, input, params, timeout);
+        alloc_stats = run_alloc_stats;
+        match outcome {
+            Outcome::Solved(result) => answer = Some(result),
+            other => return Err(other),
+        }
+
+        if iteration >= warmup {
+            samples.push(elapsed);
+        }
+    }
+
+    match answer {
+        Some(answer) => Ok(BenchResult { answer, samples, alloc_stats }),
+        None => Err(Outcome::Panicked(String::from("no iterations were run"))),
+    }
+}
+
+pub struct BenchOpts {
+    pub warmup: usize,
+    pub iterations: usize,
+
+    // Every implementation of a day side by side, instead of just the chosen one
+    pub compare: bool,
+    pub timeout: Option<Duration>,
+}
+
+// One star of one implementation in a --bench table
+pub struct BenchRow {
+    pub day: u8,
+    pub part: Part,
+    pub implementation: &'static str,
+    pub input: String,
+
+    // The stats and answer, or the outcome that stopped the bench
+    pub result: Result<(Stats, Answer), Outcome>,
+
+    // Median over the fastest median of the same star, with --compare
+    pub relative: Option<f64>,
+}
+
+pub struct BenchReport {
+    pub warmup: usize,
+    pub iterations: usize,
+    pub compare: bool,
+    pub rows: Vec<BenchRow>,
+
+    // Medians of every star that was solved, for the history file
+    pub history: Vec<HistoryEntry>,
+}
+
+impl BenchReport {
+    pub fn text (&self) -> String {
+        let rows = self.rows.iter().map(| row | {
+            let label = vec![ row.day.to_string(), row.part.to_string(), row.implementation.to_string(), row.input.clone() ];
+            let cells = match &row.result {
+                Ok((stats, answer)) => {
+                    let mut cells = vec![
+                        answer.to_string(),
+                        format_duration(stats.min),
+                        format_duration(stats.median),
+                        format_duration(stats.mean),
+                        format_duration(stats.p95),
+                        format_duration(stats.std_dev),
+                    ];
+                    if let Some(relative) = row.relative {
+                        cells.push(format!("{:.2}x", relative));
+                    }
+                    cells
+                },
+                Err(outcome) => vec![ outcome.to_string() ],
+            };
+            [ label, cells ].concat()
+        }).collect::<Vec<Vec<String>>>();
+
+        let mut headers = vec![ "Day", "Part", "Impl", "Data", "Answer", "Min", "Median", "Mean", "p95", "Std dev" ];
+        if self.compare {
+            headers.push("vs fastest");
+        }
+        format!("{} warm-up and {} measured runs per star\n{}", self.warmup, self.iterations, output::table(&headers, &rows))
+    }
+}
+
+// Both stars of `days` on each of `sources`, one star at a time so the timings are clean
+// Stars that can't run (not implemented, no input) get a single row under the day's default implementation
+pub fn run_bench (data_folder_root: &Path, days: &[u8], sources: &[InputSource], implementation: &str, params: &Params, bench_opts: &BenchOpts) -> BenchReport {
+    let mut report = BenchReport { warmup: bench_opts.warmup, iterations: bench_opts.iterations, compare: bench_opts.compare, rows: Vec::new(), history: Vec::new() };
+
+    let _quiet = runner::quiet_panics();
+    for day in days {
+        let day = *day;
+        let impls = if bench_opts.compare {
+            solution::for_day(day)
+        }
+        else {
+            solution::find(day, implementation).into_iter().collect()
+        };
+        let Some(default) = impls.first().copied() else {
+            continue;
+        };
+
+        for source in sources {
+            let params = params.for_data_set(source.data_set());
+            let input = if default.implemented() {
+                Some(source.read(data_folder_root, day))
+            }
+            else {
+                None
+            };
+
+            for part in [ Part::One, Part::Two ] {
+                let row = | solution: &'static dyn Solution, result, relative | BenchRow { day, part, implementation: solution.name(), input: source.to_string(), result, relative };
+
+                let input = match &input {
+                    None => {
+                        report.rows.push(row(default, Err(Outcome::NotImplemented), None));
+                        continue;
+                    },
+                    Some(Err(err)) => {
+                        report.rows.push(row(default, Err(Outcome::MissingInput(err.to_string())), None));
+                        continue;
+                    },
+                    Some(Ok(input)) => input,
+                };
+
+                let results = impls.iter().map(| solution | {
+                    let result = bench(*solution, part, input, &params, bench_opts.warmup, bench_opts.iterations, bench_opts.timeout);
+                    (*solution, result.map(| result | (Stats::from(&result.samples), result.answer)))
+                }).collect::<Vec<_>>();
+
+                let fastest = results.iter()
+                    .filter_map(| (_, result) | result.as_ref().ok().map(| (stats, _) | stats.median))
+                    .min();
+
+                for (solution, result) in results {
+                    let relative = match (&result, fastest, bench_opts.compare) {
+                        (Ok((stats, _)), Some(fastest), true) => Some(stats.median.as_secs_f64() / fastest.as_secs_f64().max(f64::MIN_POSITIVE)),
+                        _ => None,
+                    };
+                    if let Ok((stats, _)) = &result {
+                        report.history.push(HistoryEntry::new(day, part, solution.name(), input.raw(), stats.median, 1, "bench"));
+                    }
+                    report.rows.push(row(solution, result, relative));
+                }
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis (samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(| ms | Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn odd_sample_count () {
+        let stats = Stats::from(&millis(&[ 5, 1, 3, 2, 4 ]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn even_sample_count () {
+        let stats = Stats::from(&millis(&[ 4, 1, 3, 2 ]));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.p95, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn p95_is_nearest_rank () {
+        // 95% of 20 samples is the 19th, so the one slow outlier is left out
+        let samples = (1..=19).chain([ 1000 ]).collect::<Vec<u64>>();
+        let stats = Stats::from(&millis(&samples));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(stats.median, Duration::from_micros(10500));
+    }
+
+    #[test]
+    fn single_sample () {
+        let stats = Stats::from(&millis(&[ 7 ]));
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.p95, Duration::from_millis(7));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+}
@@ -0,0 +1,117 @@
+use std::{collections::HashMap, env, fs, io, path::{Path, PathBuf}, sync::OnceLock};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::answer::Answer;
+use crate::files;
+use crate::history;
+use crate::log::debug;
+use crate::params::Params;
+use crate::solution::Part;
+
+// Answers of single runs, so slow days don't have to be re-solved for an answer we already have
+// Opt-in with --cache (or `cache = true` in aoc.toml), stored as JSON Lines in .aoc-cache.jsonl by default:
+//
+//      {"day":10,"part":1,"implementation":"default","input_hash":"9f2c...","params":"","example":false,"build_id":"41d0...","answer":{"unsigned":7},"elapsed_nanos":1234}
+//
+// An answer is only reused by the exact same build of the runner, any rebuild (a change to a day, different
+//      features, debug vs release) gets a different `build_id`, so stale answers are never handed out
+// Entries are only ever appended, when the same key shows up more than once the last one wins
+pub const DEFAULT_CACHE_FILE: &str = ".aoc-cache.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub day: u8,
+    pub part: u8,
+    pub implementation: String,
+    pub input_hash: String,
+
+    // `--param`s the answer was found with, as `Params` displays them
+    // Whether the input was the example matters too, some days default to different parameters for it
+    pub params: String,
+    pub example: bool,
+    pub build_id: String,
+    pub answer: Answer,
+
+    // How long the run that found the answer took
+    pub elapsed_nanos: u128,
+}
+
+type CacheKey = (u8, u8, String, String, String, bool, String);
+
+impl CacheEntry {
+    pub fn new (day: u8, part: Part, implementation: &str, input: &str, params: &Params, answer: &Answer, elapsed: Duration) -> Self {
+        CacheEntry {
+            day,
+            part: match part { Part::One => 1, Part::Two => 2 },
+            implementation: implementation.to_string(),
+            input_hash: history::input_hash(input),
+            params: params.to_string(),
+            example: params.example(),
+            build_id: build_id().unwrap_or_default().to_string(),
+            answer: answer.clone(),
+            elapsed_nanos: elapsed.as_nanos(),
+        }
+    }
+
+    pub fn elapsed (&self) -> Duration {
+        Duration::from_nanos(self.elapsed_nanos.min(u64::MAX as u128) as u64)
+    }
+
+    fn key (&self) -> CacheKey {
+        (self.day, self.part, self.implementation.clone(), self.input_hash.clone(), self.params.clone(), self.example, self.build_id.clone())
+    }
+}
+
+pub struct Cache {
+    path: PathBuf,
+    entries: HashMap<CacheKey, CacheEntry>,
+}
+
+impl Cache {
+    pub fn load (path: &Path) -> Result<Self, String> {
+        if build_id().is_none() {
+            return Err(String::from("Can't cache answers, the runner's own binary couldn't be read to tell which build it is"));
+        }
+
+        // Lines written by an older version of the runner don't parse any more, their answers are from
+        //      another build and would never be handed out anyway
+        let mut entries = HashMap::new();
+        for entry in files::read_json_lines::<CacheEntry>(path)? {
+            match entry {
+                Ok(entry) => {
+                    entries.insert(entry.key(), entry);
+                },
+                Err(err) => debug!("{}, skipping it", err),
+            }
+        }
+        Ok(Cache { path: path.to_path_buf(), entries })
+    }
+
+    pub fn path (&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get (&self, day: u8, part: Part, implementation: &str, input: &str, params: &Params) -> Option<&CacheEntry> {
+        // An entry for this exact run has the same key as the one it would have been stored under
+        let key = CacheEntry::new(day, part, implementation, input, params, &Answer::from(""), Duration::ZERO).key();
+        self.entries.get(&key)
+    }
+
+    pub fn insert (&mut self, entry: CacheEntry) -> io::Result<()> {
+        files::append_json_lines(&self.path, std::slice::from_ref(&entry))?;
+        self.entries.insert(entry.key(), entry);
+        Ok(())
+    }
+}
+
+// Hash of the running executable, so every build (not just every commit) has its own answers
+// Looked up once per process, None if the binary can't be read
+pub fn build_id () -> Option<&'static str> {
+    static BUILD_ID: OnceLock<Option<String>> = OnceLock::new();
+    BUILD_ID.get_or_init(|| {
+        env::current_exe()
+            .and_then(fs::read)
+            .map(| binary | history::content_hash(&binary))
+            .ok()
+    }).as_deref()
+}
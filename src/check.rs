@@ -0,0 +1,131 @@
+use std::path::Path;
+use std::time::Duration;
+use owo_colors::Style;
+use crate::answer::Answer;
+use crate::answers::Answers;
+use crate::batch::{self, BatchInput, BatchJob};
+use crate::output;
+use crate::params::Params;
+use crate::runner::{self, DataSet, Outcome};
+use crate::solution::{self, Part, Solution};
+
+// --check: runs every star that has a recorded answer (see `answers`) and compares what it gets with it
+
+pub enum CheckResult {
+    // The day's answers.toml couldn't be read
+    BadAnswers(u8, String),
+    Star { day: u8, part: Part, data_set: DataSet, expected: Answer, outcome: Outcome },
+}
+
+impl CheckResult {
+    pub fn passed (&self) -> bool {
+        match self {
+            CheckResult::BadAnswers(_, _) => false,
+            CheckResult::Star { expected, outcome, .. } => matches!(outcome, Outcome::Solved(actual) if actual == expected),
+        }
+    }
+}
+
+impl std::fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pass = output::paint("PASS", Style::new().green().bold());
+        let fail = output::paint("FAIL", Style::new().red().bold());
+        match self {
+            CheckResult::BadAnswers(day, err) => write!(f, "Day {:>2}: {} {}", day, fail, err),
+            CheckResult::Star { day, part, data_set, expected, outcome } => {
+                write!(f, "Day {:>2} star {} ({}): ", day, part, data_set)?;
+                match outcome {
+                    Outcome::Solved(actual) if actual == expected => write!(f, "{} {}", pass, actual),
+                    Outcome::Solved(actual) => write!(f, "{} expected {}, got {}", fail, expected, actual),
+                    other => write!(f, "{} expected {}, but {}", fail, expected, other),
+                }
+            },
+        }
+    }
+}
+
+pub struct CheckReport {
+    pub results: Vec<CheckResult>,
+
+    // Stars without a recorded answer
+    pub skipped: usize,
+}
+
+impl CheckReport {
+    pub fn passed (&self) -> usize {
+        self.results.iter().filter(| result | result.passed()).count()
+    }
+
+    pub fn failed (&self) -> usize {
+        self.results.len() - self.passed()
+    }
+
+    pub fn text (&self) -> String {
+        let mut lines = self.results.iter().map(| result | result.to_string()).collect::<Vec<String>>();
+        lines.push(String::new());
+        lines.push(format!("{} passed, {} failed, {} skipped (no recorded answer)", self.passed(), self.failed(), self.skipped));
+        lines.join("\n")
+    }
+}
+
+// A `CheckResult` before its star has run
+enum CheckLine {
+    BadAnswers(u8, String),
+    Star { day: u8, part: Part, data_set: DataSet, expected: Answer },
+}
+
+// Both stars of `days` on both data sets, with the implementation named `implementation`
+pub fn check (data_folder_root: &Path, days: &[u8], implementation: &str, threads: usize, timeout: Option<Duration>) -> CheckReport {
+    let mut skipped = 0;
+
+    // Everything but the outcomes is known up front, so the runs can happen in any order
+    let mut lines: Vec<CheckLine> = Vec::new();
+    let mut inputs: Vec<(Part, DataSet, &'static dyn Solution, BatchInput)> = Vec::new();
+    for day in days {
+        let day = *day;
+        let Some(solution) = solution::find(day, implementation) else {
+            continue;
+        };
+
+        let answers = match Answers::load(data_folder_root, day) {
+            Ok(answers) => answers,
+            Err(err) => {
+                lines.push(CheckLine::BadAnswers(day, err));
+                continue;
+            }
+        };
+
+        for data_set in [ DataSet::Example, DataSet::Full ] {
+            for part in [ Part::One, Part::Two ] {
+                let Some(expected) = answers.expected(data_set, part) else {
+                    skipped += 1;
+                    continue;
+                };
+
+                let input = if solution.implemented() {
+                    Some(runner::read_input(&runner::data_path(data_folder_root, day, data_set)).map_err(| err | err.to_string()))
+                }
+                else {
+                    None
+                };
+                lines.push(CheckLine::Star { day, part, data_set, expected });
+                inputs.push((part, data_set, solution, input));
+            }
+        }
+    }
+
+    let jobs = inputs.iter()
+        .map(| (part, data_set, solution, input) | BatchJob { solution: *solution, part: *part, params: Params::default().for_data_set(Some(*data_set)), input: input.as_ref() })
+        .collect::<Vec<BatchJob>>();
+
+    let _quiet = runner::quiet_panics();
+    let mut outcomes = batch::run_batch(jobs, threads, timeout).into_iter().map(| (outcome, _, _, _) | outcome);
+    let results = lines.into_iter().map(| line | match line {
+        CheckLine::BadAnswers(day, err) => CheckResult::BadAnswers(day, err),
+        CheckLine::Star { day, part, data_set, expected } => {
+            CheckResult::Star { day, part, data_set, expected, outcome: outcomes.next().expect("Every star has an outcome") }
+        },
+    }).collect();
+
+    CheckReport { results, skipped }
+}
@@ -0,0 +1,104 @@
+use std::{collections::BTreeMap, env, path::{Path, PathBuf}};
+use serde::Deserialize;
+use crate::files;
+
+// Runner defaults that would otherwise be retyped on every invocation, from an aoc.toml like:
+//
+//      [defaults]
+//      input_folder = "../aoc-data"
+//      timeout = "30s"
+//      color = "never"
+//
+//      [day.10]
+//      impl = "iterative"
+//      timeout = "2m"
+//      params = { max_depth = 12 }
+//
+// A day's table overrides `[defaults]` for runs of that day, and flags given on the command line override both
+// Settings are kept as they were written, main parses them the same way it parses the flags they stand in for
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    defaults: Settings,
+    #[serde(default)]
+    day: BTreeMap<u8, Settings>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    pub input_folder: Option<String>,
+    #[serde(rename = "impl")]
+    pub implementation: Option<String>,
+    pub timeout: Option<String>,
+    pub color: Option<String>,
+    pub format: Option<String>,
+    pub jobs: Option<usize>,
+    pub history_file: Option<String>,
+    pub cache: Option<bool>,
+    pub cache_file: Option<String>,
+    #[serde(default)]
+    pub params: BTreeMap<String, toml::Value>,
+}
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+// The working directory's aoc.toml wins over the one in $XDG_CONFIG_HOME (or ~/.config when that isn't set)
+pub fn find () -> Option<PathBuf> {
+    let local = PathBuf::from(CONFIG_FILE);
+    if local.is_file() {
+        return Some(local);
+    }
+
+    let config_home = match env::var_os("XDG_CONFIG_HOME") {
+        Some(config_home) if !config_home.is_empty() => PathBuf::from(config_home),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    let global = config_home.join(CONFIG_FILE);
+    global.is_file().then_some(global)
+}
+
+impl Config {
+    pub fn load (path: &Path) -> Result<Self, String> {
+        let Some(contents) = files::read_optional(path)? else {
+            return Ok(Config::default());
+        };
+        toml::from_str(&contents).map_err(| err | format!("Could not parse '{}': {}", path.display(), err))
+    }
+
+    // `[defaults]`, with `[day.<day>]` on top of it
+    pub fn settings (&self, day: Option<u8>) -> Settings {
+        let defaults = self.defaults.clone();
+        let Some(overrides) = day.and_then(| day | self.day.get(&day)) else {
+            return defaults;
+        };
+
+        let mut params = defaults.params;
+        params.extend(overrides.params.clone());
+        Settings {
+            input_folder: overrides.input_folder.clone().or(defaults.input_folder),
+            implementation: overrides.implementation.clone().or(defaults.implementation),
+            timeout: overrides.timeout.clone().or(defaults.timeout),
+            color: overrides.color.clone().or(defaults.color),
+            format: overrides.format.clone().or(defaults.format),
+            jobs: overrides.jobs.or(defaults.jobs),
+            history_file: overrides.history_file.clone().or(defaults.history_file),
+            cache: overrides.cache.or(defaults.cache),
+            cache_file: overrides.cache_file.clone().or(defaults.cache_file),
+            params,
+        }
+    }
+}
+
+impl Settings {
+    // Parameters as `--param` would have given them, TOML strings without their quotes
+    pub fn param_pairs (&self) -> Vec<(String, String)> {
+        self.params.iter()
+            .map(| (key, value) | match value {
+                toml::Value::String(text) => (key.clone(), text.clone()),
+                other => (key.clone(), other.to_string()),
+            })
+            .collect()
+    }
+}
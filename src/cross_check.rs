@@ -0,0 +1,146 @@
+use std::path::Path;
+use std::time::Duration;
+use owo_colors::Style;
+use crate::alloc::AllocStats;
+use crate::output;
+use crate::params::Params;
+use crate::runner::{self, InputSource, Outcome, format_duration};
+use crate::solution::{self, Part};
+
+// --cross-check: runs every implementation of a day on the same input and compares their answers (and timings)
+// Only days with more than one implementation, all of them implemented, have anything to compare
+
+// How long each run gets when there's no --timeout
+// Some implementations are slow on purpose (day 7's naive one), so a cross-check always has a limit, and an
+//      implementation that hits it fails the check like one that panics
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub enum CrossCheckRow {
+    MissingInput { day: u8, input: String, err: String },
+    Run {
+        day: u8,
+        part: Part,
+        implementation: &'static str,
+        input: String,
+        outcome: Outcome,
+        elapsed: Duration,
+        alloc_stats: Option<AllocStats>,
+
+        // Elapsed over the fastest implementation that solved the same star, None when this one didn't
+        relative: Option<f64>,
+    },
+}
+
+// A star where the implementations got different answers, or where any of them didn't get one at all
+pub struct Disagreement {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl std::fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let answers = self.outcomes.iter()
+            .map(| (name, outcome) | format!("{} = {}", name, outcome))
+            .collect::<Vec<String>>();
+        write!(f, "Day {:>2} star {} ({}): {}", self.day, self.part, self.input, answers.join(", "))
+    }
+}
+
+pub struct CrossCheckReport {
+    pub rows: Vec<CrossCheckRow>,
+    pub disagreements: Vec<Disagreement>,
+}
+
+impl CrossCheckReport {
+    // Whether every implementation solved every star, with the same answers
+    pub fn agrees (&self) -> bool {
+        self.disagreements.is_empty()
+    }
+
+    pub fn text (&self) -> String {
+        let rows = self.rows.iter().map(| row | match row {
+            CrossCheckRow::MissingInput { day, input, err } => {
+                vec![ day.to_string(), String::from("-"), String::from("-"), input.clone(), Outcome::MissingInput(err.clone()).to_string() ]
+            },
+            CrossCheckRow::Run { day, part, implementation, input, outcome, elapsed, alloc_stats, relative } => [ vec![
+                day.to_string(),
+                part.to_string(),
+                implementation.to_string(),
+                input.clone(),
+                outcome.to_string(),
+                format_duration(*elapsed),
+                relative.map(| relative | format!("{:.2}x", relative)).unwrap_or(String::from("-")),
+            ], output::memory_cell(*alloc_stats) ].concat(),
+        }).collect::<Vec<Vec<String>>>();
+
+        let mut text = output::table(&output::with_memory_column(vec![ "Day", "Part", "Impl", "Data", "Answer", "Elapsed", "vs fastest" ]), &rows);
+        text += "\n\n";
+        if self.agrees() {
+            text += &output::paint("All implementations agree", Style::new().green().bold());
+        }
+        else {
+            text += &output::paint("Implementations disagree:", Style::new().red().bold());
+            for disagreement in &self.disagreements {
+                text += &format!("\n    {}", disagreement);
+            }
+        }
+        text
+    }
+}
+
+// Every implementation of each of `days` on each of `sources`, one at a time so the timings compare
+pub fn cross_check (data_folder_root: &Path, days: &[u8], sources: &[InputSource], params: &Params, timeout: Duration) -> CrossCheckReport {
+    let mut report = CrossCheckReport { rows: Vec::new(), disagreements: Vec::new() };
+
+    let _quiet = runner::quiet_panics();
+    for day in days {
+        let day = *day;
+        let impls = solution::for_day(day);
+        if impls.len() < 2 || !impls.iter().all(| solution | solution.implemented()) {
+            continue;
+        }
+
+        for source in sources {
+            let params = params.for_data_set(source.data_set());
+            let input = match source.read(data_folder_root, day) {
+                Ok(input) => input,
+                Err(err) => {
+                    report.rows.push(CrossCheckRow::MissingInput { day, input: source.to_string(), err: err.to_string() });
+                    continue;
+                }
+            };
+
+            for part in [ Part::One, Part::Two ] {
+                let results = impls.iter()
+                    .map(| solution | (*solution, runner::run_with_timeout(*solution, part, &input, &params, Some(timeout))))
+                    .collect::<Vec<_>>();
+
+                let first = match &results[0].1.0 {
+                    Outcome::Solved(answer) => Some(answer),
+                    _ => None,
+                };
+                let agree = results.iter().all(| (_, (outcome, _, _, _)) | matches!((outcome, first), (Outcome::Solved(answer), Some(first)) if answer == first));
+                if !agree {
+                    let outcomes = results.iter().map(| (solution, (outcome, _, _, _)) | (solution.name(), outcome.clone())).collect();
+                    report.disagreements.push(Disagreement { day, part, input: source.to_string(), outcomes });
+                }
+
+                let fastest = results.iter()
+                    .filter(| (_, (outcome, _, _, _)) | matches!(outcome, Outcome::Solved(_)))
+                    .map(| (_, (_, elapsed, _, _)) | *elapsed)
+                    .min();
+
+                for (solution, (outcome, elapsed, alloc_stats, _)) in results {
+                    let relative = match (&outcome, fastest) {
+                        (Outcome::Solved(_), Some(fastest)) => Some(elapsed.as_secs_f64() / fastest.as_secs_f64().max(f64::MIN_POSITIVE)),
+                        _ => None,
+                    };
+                    report.rows.push(CrossCheckRow::Run { day, part, implementation: solution.name(), input: source.to_string(), outcome, elapsed, alloc_stats, relative });
+                }
+            }
+        }
+    }
+    report
+}
@@ -1,24 +1,54 @@
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::params::Params;
+use crate::phase;
+use crate::solution::solution;
+
+solution!(Day1, day = 1, params);
+
+// `--param dial_size=N` and `--param start=N`, the puzzle's dial goes 0 to 99 and starts pointing at 50
+pub struct Day1Params {
+    pub dial_size: i32,
+    pub start: i32,
+}
+
+impl Day1Params {
+    pub fn from (params: &Params) -> AocResult<Self> {
+        params.expect_keys(&[ "dial_size", "start" ])?;
+        let dial_size = params.get("dial_size", 100)?;
+        let start = params.get("start", 50)?;
+
+        if dial_size <= 0 {
+            return Err(AocError::new(format!("The dial needs at least one number on it, but dial_size is {}", dial_size)));
+        }
+        if start < 0 || start >= dial_size {
+            return Err(AocError::new(format!("The dial has to start on one of its numbers (0 to {}), but start is {}", dial_size - 1, start)));
+        }
+        Ok(Day1Params { dial_size, start })
+    }
+}
+
 // Max = 99
 #[derive(PartialEq, Eq)]
-enum Direction {
+pub enum Direction {
     R, // R => Positive
     L  // L => Negative
 }
 
 impl Direction {
-    fn from(c: char) -> Self {
+    pub fn from(c: char) -> AocResult<Self> {
         if c == 'R' {
-            Direction::R
+            Ok(Direction::R)
         }
         else if c == 'L' {
-            Direction::L
+            Ok(Direction::L)
         }
         else {
-            panic!("Direction::from expects only 'R' or 'L' character inputs, but recieved {}", c);
+            Err(AocError::new(format!("Direction::from expects only 'R' or 'L' character inputs, but recieved {}", c)))
         }
     }
 
-    fn get_sign (&self) -> i32 {
+    pub fn get_sign (&self) -> i32 {
         if *self == Direction::L {
             -1
         }
@@ -28,26 +58,43 @@ impl Direction {
     }
 }
 
-pub fn star_one (input: String) -> String {
-    let start = 50;
-
-    let min = 0;
-    let max = 99;
-    let digits = 100;
+// One rotation per line, like 'L68'
+fn parse_rotation (line: usize, rotation: &str) -> AocResult<(Direction, i32)> {
+    let mut chars = rotation.chars();
+    let Some(direction_chr) = chars.next() else {
+        return Err(AocError::on_line(line, "Empty line, expected a rotation like 'L68'"));
+    };
+    let direction = Direction::from(direction_chr).map_err(| err | err.with_position(line, 1))?;
+
+    let value_str = chars.as_str();
+    let value_res = value_str.parse::<u16>();
+    let value = match value_res {
+        Ok(parsed) => parsed,
+        Err(err) => return Err(AocError::at(line, 2, format!("Parsing error for value '{value_str}': {err}"))),
+    } as i32;
+
+    Ok((direction, value))
+}
 
-    let (count_landed_at_zero, _) = input.lines()
+fn parse_rotations (input: &str) -> AocResult<Vec<(Direction, i32)>> {
+    input.lines()
         .enumerate()
-        .fold((0usize, start), | (count_acc, dial_position), (line_num, curr_line) | {
+        .map(| (line_num, line) | parse_rotation(line_num + 1, line))
+        .collect()
+}
+
+#[allow(clippy::needless_return, clippy::neg_multiply)]
+pub fn star_one (input: &str, params: &Params) -> AocResult<Answer> {
+    let Day1Params { dial_size, start } = Day1Params::from(params)?;
 
-            let mut chars = curr_line.chars();
-            let direction = Direction::from(chars.next().unwrap());
+    let min = 0;
+    let max = dial_size - 1;
+    let digits = dial_size;
+
+    let rotations = phase::parse(|| parse_rotations(input))?;
 
-            let value_str: String = chars.collect();
-            let value_res = value_str.parse::<u16>();
-            let value = match value_res {
-                Ok(parsed) => parsed,
-                Err(err) => panic!("Parsing error for line {line_num} value '{value_str}': {err}"),
-            } as i32;
+    let (count_landed_at_zero, _) = rotations.into_iter()
+        .fold((0usize, start), | (count_acc, dial_position), (direction, value) | {
 
             let value = value % digits;
 
@@ -59,7 +106,7 @@ pub fn star_one (input: String) -> String {
             }
             else if rotation_res < min {
                 let post_rotate = max - ((rotation_res * -1) % digits) + 1;
-                if post_rotate != 100 {
+                if post_rotate != digits {
                     post_rotate
                 }
                 else { 0 }
@@ -71,29 +118,21 @@ pub fn star_one (input: String) -> String {
             return (count_acc + if next == 0 { 1 } else { 0 }, next);
         });
 
-    return count_landed_at_zero.to_string();
+    return Ok(count_landed_at_zero.into());
 }
 
-pub fn star_two (input: String) -> String {
-    let start = 50;
+#[allow(clippy::needless_return, clippy::neg_multiply)]
+pub fn star_two (input: &str, params: &Params) -> AocResult<Answer> {
+    let Day1Params { dial_size, start } = Day1Params::from(params)?;
 
     let min = 0;
-    let max = 99;
-    let digits = 100;
-
-    let (count_landed_at_zero, _dial) = input.lines()
-        .enumerate()
-        .fold((0usize, start), | (count_acc, dial_position), (line_num, curr_line) | {
+    let max = dial_size - 1;
+    let digits = dial_size;
 
-            let mut chars = curr_line.chars();
-            let direction = Direction::from(chars.next().unwrap());
+    let rotations = phase::parse(|| parse_rotations(input))?;
 
-            let value_str: String = chars.collect();
-            let value_res = value_str.parse::<u16>();
-            let value = match value_res {
-                Ok(parsed) => parsed,
-                Err(err) => panic!("Parsing error for line {line_num} value '{value_str}': {err}"),
-            } as i32;
+    let (count_landed_at_zero, _dial) = rotations.into_iter()
+        .fold((0usize, start), | (count_acc, dial_position), (direction, value) | {
 
             let mut passed_zero = value / digits;
             let value = value % digits;
@@ -126,7 +165,7 @@ pub fn star_two (input: String) -> String {
                     passed_zero += 1;
                 }
 
-                if post_rotate != 100 {
+                if post_rotate != digits {
                     post_rotate
                 }
                 else { 
@@ -142,6 +181,6 @@ pub fn star_two (input: String) -> String {
             return (count_passed_zero, next);
         });
 
-    return count_landed_at_zero.to_string();
+    return Ok(count_landed_at_zero.into());
 }
 
@@ -1,7 +1,41 @@
-use std::{collections::HashSet, thread::sleep, time::Duration, u16, usize};
+use std::collections::HashSet;
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::params::Params;
+use crate::phase;
+use crate::solution::solution;
+
+solution!(Day10, day = 10, params);
+solution!(Day10Iterative, day = 10, name = "iterative", star_one = star_one_iterative, star_two = star_two, params);
+
+// `--param max_depth=N`, the most button presses star one tries before giving up on a machine
+pub struct Day10Params {
+    pub max_depth: usize,
+}
+
+impl Day10Params {
+    pub fn from (params: &Params) -> AocResult<Self> {
+        params.expect_keys(&[ "max_depth" ])?;
+        let max_depth = params.get("max_depth", 10)?;
 
+        if max_depth == 0 {
+            return Err(AocError::new("max_depth has to be at least 1"));
+        }
+        Ok(Day10Params { max_depth })
+    }
+}
 
-struct Machine {
+// Adds up star one's presses, a machine that needs more than `max_depth` of them can't be solved
+fn sum_indicator_presses (presses: impl Iterator<Item = usize>, max_depth: usize) -> AocResult<Answer> {
+    presses.enumerate().try_fold(0usize, | presses_acc, (machine_idx, presses) | {
+        if presses == usize::MAX {
+            return Err(AocError::on_line(machine_idx + 1, format!("This machine needs more than {} button presses (max_depth) to reach its indicator lights", max_depth)));
+        }
+        Ok(presses_acc + presses)
+    }).map(Answer::from)
+}
+
+pub struct Machine {
     desired_state: u16,
     state: u16,
     buttons: Vec<u16>,
@@ -10,14 +44,23 @@ struct Machine {
 }
 
 impl Machine {
-    fn from (line: &str) -> Self {
+    // Errors only carry the column, the caller knows which line this was
+    #[allow(clippy::assign_op_pattern, clippy::len_zero)]
+    pub fn from (line: &str) -> AocResult<Self> {
 
         let machine_reg = regex::Regex::new(r"\[(?<desired_state>[.#]+)\] (?<buttons>(\((\d+,?)+\) )+)\{(?<joltage_requirement>(\d+,?)+)\}").unwrap();
-        let cap = machine_reg.captures(line).unwrap();
+        let Some(cap) = machine_reg.captures(line) else {
+            return Err(AocError::new("Expected a machine in the form '[.##.] (0,1) (2) {3,4}'"));
+        };
         let desired_states = cap.name("desired_state").unwrap();
         let buttons = cap.name("buttons").unwrap();
         let joltage_requirement = cap.name("joltage_requirement").unwrap();
 
+        // Everything is packed into u16s, so there's only room for 16 indicator lights
+        if desired_states.len() > 16 {
+            return Err(AocError::new(format!("Machines can have at most 16 indicator lights, but this one has {}", desired_states.len())).with_column(desired_states.start() + 1));
+        }
+
         let initial_state = 0u16;
         let desired_state = desired_states.as_str().chars().rev().fold(0u16, | mut acc, state | {
             acc = acc << 1;
@@ -34,40 +77,52 @@ impl Machine {
             .split(" ")
             .filter(| button | button.len() > 0)
             .map(| button | {
+                // Column of the button's opening parenthesis
+                let column = button.as_ptr() as usize - line.as_ptr() as usize + 1;
                 let button = &button[1..button.len()-1];
-                let indicators = button.split(',');
+                let mut indicators = button.split(',');
                 
-                indicators.fold(0u16, | acc, indicator | {
-                    acc | (1 << indicator.parse::<u16>().unwrap())
+                indicators.try_fold(0u16, | acc, indicator | {
+                    match indicator.parse::<u16>() {
+                        Ok(indicator) if indicator < 16 => Ok(acc | (1 << indicator)),
+                        _ => Err(AocError::new(format!("Buttons can only toggle indicators 0 through 15, not '{}'", indicator)).with_column(column)),
+                    }
                 })
-            }).collect();
+            }).collect::<AocResult<Vec<u16>>>()?;
 
         let mut max_joltage_depth = 0;
         let mut joltage_requirement_arr = [0; 16];
 
+        let joltage_column = joltage_requirement.start() + 1;
         let joltage_requirement_str = joltage_requirement.as_str().split(",");
         for (idx, jrs) in joltage_requirement_str.into_iter().enumerate() {
-            let joltage = jrs.parse::<u8>().unwrap();
+            if idx >= joltage_requirement_arr.len() {
+                return Err(AocError::new(format!("Machines can have at most {} joltage requirements", joltage_requirement_arr.len())).with_column(joltage_column));
+            }
+            let joltage = jrs.parse::<u8>().map_err(| err | {
+                AocError::new(format!("Could not parse joltage requirement '{}': {}", jrs, err)).with_column(joltage_column)
+            })?;
             joltage_requirement_arr[idx] = joltage;
             max_joltage_depth += joltage as usize;
         }
 
-        Machine {
+        Ok(Machine {
             buttons,
             desired_state,
             joltage_requirement: joltage_requirement_arr,
             max_joltage_depth,
             state: initial_state
-        }
+        })
     }
 
-    fn get_min_indicator_button_presses (self) -> usize {
-        self.get_min_indicator_presses_recurse(self.desired_state, None, u16::MAX, 0, HashSet::new())
+    pub fn get_min_indicator_button_presses (self, max_depth: usize) -> usize {
+        self.get_min_indicator_presses_recurse(self.desired_state, None, u16::MAX, 0, max_depth, HashSet::new())
     }
 
     // Naive recursive memoized search for pt 1 -- ~15 second runtime on my machine
-    fn get_min_indicator_presses_recurse (&self, state: u16, mut min: Option<usize>, prev_choice: u16, depth: usize, memo: HashSet<u16>) -> usize {
-        if depth == 10 {
+    #[allow(clippy::collapsible_if, clippy::needless_return)]
+    fn get_min_indicator_presses_recurse (&self, state: u16, mut min: Option<usize>, prev_choice: u16, depth: usize, max_depth: usize, memo: HashSet<u16>) -> usize {
+        if depth >= max_depth {
             return usize::MAX;
         }
 
@@ -95,7 +150,7 @@ impl Machine {
 
             let mut memo = memo.clone();
             memo.insert(next_state);
-            let res = self.get_min_indicator_presses_recurse(next_state, min, button, depth + 1, memo);
+            let res = self.get_min_indicator_presses_recurse(next_state, min, button, depth + 1, max_depth, memo);
 
             if let Some(min_val) = min {
                 if res < min_val {
@@ -110,16 +165,17 @@ impl Machine {
     }
 
     // Iterative version -- 15 seconds as well... *shrug*
-    fn get_min_indicator_button_presses_iterative (self) -> usize {
+    #[allow(clippy::collapsible_if, clippy::needless_range_loop, clippy::needless_return)]
+    pub fn get_min_indicator_button_presses_iterative (self, max_depth: usize) -> usize {
         let mut min: Option<usize> = None;
 
-        let dp: [Vec<(          // All explorable states at depth
+        let dp: Vec<Vec<(       // All explorable states at depth
             u16,                    // Current state
             u16,                    // previous choice
             HashSet<u16>,           // Tracks states visited
-        )>; 10] = std::array::from_fn(|_| vec![(self.desired_state, u16::MAX, HashSet::new())]);
+        )>> = (0..max_depth).map(|_| vec![(self.desired_state, u16::MAX, HashSet::new())]).collect();
 
-        for depth in 0..10 {
+        for depth in 0..max_depth {
 
             if let Some(min_val) = min {
                 if min_val < depth {
@@ -147,7 +203,7 @@ impl Machine {
         
                     let mut memo = memo.clone();
                     memo.insert(next_state);
-                    let res = self.get_min_indicator_presses_recurse(next_state, min, button, depth + 1, memo);
+                    let res = self.get_min_indicator_presses_recurse(next_state, min, button, depth + 1, max_depth, memo);
         
                     if let Some(min_val) = min {
                         if res < min_val {
@@ -167,11 +223,12 @@ impl Machine {
 
 
 
-    fn get_min_joltage_requirement_presses (self) -> usize {
+    pub fn get_min_joltage_requirement_presses (self) -> usize {
         self.get_min_joltage_requirement_presses_recurse([0; 16], None, 0)
     }
 
     // Naive recursive memoized search for pt 1 -- ~15 second runtime on my machine
+    #[allow(clippy::clone_on_copy, clippy::collapsible_if, clippy::needless_return)]
     fn get_min_joltage_requirement_presses_recurse (&self, state: [u8; 16], mut min: Option<usize>, depth: usize) -> usize {
         if depth >= self.max_joltage_depth / 10 {
             return usize::MAX;
@@ -254,6 +311,7 @@ impl std::fmt::Display for Machine {
     }
 }
 
+#[allow(unused)]
 fn count_bits_in_num (mut num: u16) -> u16 {
     let mut count = 0;
     while num > 0 {
@@ -264,21 +322,38 @@ fn count_bits_in_num (mut num: u16) -> u16 {
 }
 
 
-pub fn star_one (input: String) -> String {
+fn parse_machines (input: &str) -> AocResult<Vec<Machine>> {
     input
         .lines()
-        .map(Machine::from)
-        .map(Machine::get_min_indicator_button_presses)
-        .sum::<usize>()
-        .to_string()
+        .enumerate()
+        .map(| (line_idx, line) | Machine::from(line).map_err(| err | err.with_line(line_idx + 1)))
+        .collect()
 }
 
-pub fn star_two (input: String) -> String {
-    input
-        .lines()
-        .map(Machine::from)
+pub fn star_one (input: &str, params: &Params) -> AocResult<Answer> {
+    let Day10Params { max_depth } = Day10Params::from(params)?;
+    let presses = phase::parse(|| parse_machines(input))?
+        .into_iter()
+        .map(| machine | machine.get_min_indicator_button_presses(max_depth));
+    sum_indicator_presses(presses, max_depth)
+}
+
+pub fn star_one_iterative (input: &str, params: &Params) -> AocResult<Answer> {
+    let Day10Params { max_depth } = Day10Params::from(params)?;
+    let presses = phase::parse(|| parse_machines(input))?
+        .into_iter()
+        .map(| machine | machine.get_min_indicator_button_presses_iterative(max_depth));
+    sum_indicator_presses(presses, max_depth)
+}
+
+// Only star one's search has a depth cap, star two takes the same keys so either star can be run with them
+pub fn star_two (input: &str, params: &Params) -> AocResult<Answer> {
+    Day10Params::from(params)?;
+
+    Ok(phase::parse(|| parse_machines(input))?
+        .into_iter()
         .map(Machine::get_min_joltage_requirement_presses)
         .sum::<usize>()
-        .to_string()
+        .into())
 }
 
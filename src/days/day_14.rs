@@ -1,9 +1,14 @@
+use crate::answer::Answer;
+use crate::error::AocResult;
+use crate::solution::solution;
 
-pub fn star_one (input: String) -> String {
+solution!(Day14, day = 14, unimplemented);
+
+pub fn star_one (_input: &str) -> AocResult<Answer> {
     todo!()
 }
 
-pub fn star_two (input: String) -> String {
+pub fn star_two (_input: &str) -> AocResult<Answer> {
     todo!()
 }
 
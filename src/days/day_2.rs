@@ -1,15 +1,41 @@
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::phase;
+use crate::solution::solution;
+
+solution!(Day2, day = 2);
+
+// Parses one "start-end" range out of the comma separated input
+// The whole `input` is only needed to work out where in it a bad range is
+pub fn parse_range (input: &str, curr_range: &str) -> AocResult<(u128, u128)> {
+    let offset = curr_range.as_ptr() as usize - input.as_ptr() as usize;
+
+    let mut splt = curr_range.split('-');
+    let sstr = splt.next().unwrap_or("");
+    let Some(estr) = splt.next() else {
+        return Err(AocError::at_offset(input, offset, format!("Expected a range like '11-22', but got '{}'", curr_range)));
+    };
+
+    let start = sstr.parse::<u128>()
+        .map_err(| err | AocError::at_offset(input, offset, format!("Invalid range start '{}': {}", sstr, err)))?;
+    let end = estr.parse::<u128>()
+        .map_err(| err | AocError::at_offset(input, offset + sstr.len() + 1, format!("Invalid range end '{}': {}", estr, err)))?;
+
+    Ok((start, end))
+}
 
-pub fn star_one (input: String) -> String {
-    let invalid_ids = input.split(',').enumerate()
-        .fold(0u128, | invalid_acc, (_, curr_range)| {
-            
-            let mut  splt = curr_range.split('-');
-            let sstr = splt.next().unwrap();
-            let estr = splt.next().unwrap();
+pub fn parse_ranges (input: &str) -> AocResult<Vec<(u128, u128)>> {
+    input.split(',')
+        .map(| curr_range | parse_range(input, curr_range))
+        .collect()
+}
 
-            let start = sstr.parse::<u128>().unwrap();
-            let end = estr.parse::<u128>().unwrap();
+#[allow(clippy::needless_return)]
+pub fn star_one (input: &str) -> AocResult<Answer> {
+    let ranges = phase::parse(|| parse_ranges(input))?;
 
+    let invalid_ids = ranges.into_iter()
+        .fold(0u128, | invalid_acc, (start, end) | {
             (start..=end).fold(invalid_acc, | range_acc, n | {
                 let nstr = n.to_string();
                 if nstr.len() % 2 != 0{
@@ -42,20 +68,15 @@ pub fn star_one (input: String) -> String {
             })
         });
 
-    invalid_ids.to_string()
+    Ok(invalid_ids.into())
 }
 
-pub fn star_two (input: String) -> String {
-    let invalid_ids = input.split(',').enumerate()
-        .fold(0u128, | invalid_acc, (_, curr_range)| {
-            
-            let mut  splt = curr_range.split('-');
-            let sstr = splt.next().unwrap();
-            let estr = splt.next().unwrap();
-
-            let start = sstr.parse::<u128>().unwrap();
-            let end = estr.parse::<u128>().unwrap();
+#[allow(clippy::needless_return)]
+pub fn star_two (input: &str) -> AocResult<Answer> {
+    let ranges = phase::parse(|| parse_ranges(input))?;
 
+    let invalid_ids = ranges.into_iter()
+        .fold(0u128, | invalid_acc, (start, end) | {
             (start..=end).fold(invalid_acc, | range_acc, n | {
                 let nstr = n.to_string();
 
@@ -87,6 +108,6 @@ pub fn star_two (input: String) -> String {
             })
         });
 
-    invalid_ids.to_string()
+    Ok(invalid_ids.into())
 }
 
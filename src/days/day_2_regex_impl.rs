@@ -1,16 +1,17 @@
 use fancy_regex::Regex;
+use crate::answer::Answer;
+use crate::days::day_2::parse_ranges;
+use crate::error::AocResult;
+use crate::phase;
+use crate::solution::solution;
 
-pub fn star_one (input: String) -> String {
-    let invalid_ids = input.split(',').enumerate()
-        .fold(0u128, | invalid_acc, (_, curr_range)| {
-            
-            let mut  splt = curr_range.split('-');
-            let sstr = splt.next().unwrap();
-            let estr = splt.next().unwrap();
+solution!(Day2Regex, day = 2, name = "regex");
 
-            let start = sstr.parse::<u128>().unwrap();
-            let end = estr.parse::<u128>().unwrap();
+pub fn star_one (input: &str) -> AocResult<Answer> {
+    let ranges = phase::parse(|| parse_ranges(input))?;
 
+    let invalid_ids = ranges.into_iter()
+        .fold(0u128, | invalid_acc, (start, end) | {
             (start..=end).fold(invalid_acc, | range_acc, n | {
                 let nstr = n.to_string();
                 if nstr.len() % 2 != 0{
@@ -28,20 +29,14 @@ pub fn star_one (input: String) -> String {
             })
         });
 
-    invalid_ids.to_string()
+    Ok(invalid_ids.into())
 }
 
-pub fn star_two (input: String) -> String {
-    let invalid_ids = input.split(',').enumerate()
-        .fold(0u128, | invalid_acc, (_, curr_range)| {
-            
-            let mut  splt = curr_range.split('-');
-            let sstr = splt.next().unwrap();
-            let estr = splt.next().unwrap();
-
-            let start = sstr.parse::<u128>().unwrap();
-            let end = estr.parse::<u128>().unwrap();
+pub fn star_two (input: &str) -> AocResult<Answer> {
+    let ranges = phase::parse(|| parse_ranges(input))?;
 
+    let invalid_ids = ranges.into_iter()
+        .fold(0u128, | invalid_acc, (start, end) | {
             (start..=end).fold(invalid_acc, | range_acc, n | {
                 let nstr = n.to_string();
 
@@ -56,6 +51,6 @@ pub fn star_two (input: String) -> String {
             })
         });
 
-    invalid_ids.to_string()
+    Ok(invalid_ids.into())
 }
 
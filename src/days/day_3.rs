@@ -1,5 +1,32 @@
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::params::Params;
+use crate::phase;
+use crate::solution::solution;
 
-fn get_bank_joltage (bank: &[u8], batteries_count: usize) -> u64 {
+solution!(Day3, day = 3, params);
+
+// `--param batteries=N`, how many batteries to turn on in each bank
+// The puzzle turns on 2 for star one and 12 for star two, so each star passes in its own default
+pub struct Day3Params {
+    pub batteries: usize,
+}
+
+impl Day3Params {
+    pub fn from (params: &Params, default_batteries: usize) -> AocResult<Self> {
+        params.expect_keys(&[ "batteries" ])?;
+        let batteries = params.get("batteries", default_batteries)?;
+
+        // Any more than 19 digits doesn't fit in a u64
+        if batteries == 0 || batteries > 19 {
+            return Err(AocError::new(format!("batteries has to be between 1 and 19, but got {}", batteries)));
+        }
+        Ok(Day3Params { batteries })
+    }
+}
+
+#[allow(clippy::needless_return)]
+pub fn get_bank_joltage (bank: &[u8], batteries_count: usize) -> u64 {
     let mut remaining_batteries = batteries_count;
     let mut current_idx = 0;
 
@@ -14,6 +41,7 @@ fn get_bank_joltage (bank: &[u8], batteries_count: usize) -> u64 {
     return joltage;
 }
 
+#[allow(clippy::needless_return)]
 fn get_best_start (bank: &[u8], batteries_count: usize) -> usize {
     let mut best_start = 0;
     let mut best_start_idx = 0;
@@ -30,24 +58,41 @@ fn get_best_start (bank: &[u8], batteries_count: usize) -> usize {
 }
 
 
-pub fn star_one (input: String) -> String {
-    input.lines().fold(0u64, | joltage_acc, bank | {
-        let bank_bytes = bank.as_bytes().iter().map(| battery_chr | {
-            return battery_chr - b'0';
-        }).collect::<Vec<u8>>();
+// Every battery in a bank is a single digit joltage
+fn parse_bank (line: usize, bank: &str) -> AocResult<Vec<u8>> {
+    bank.chars().enumerate().map(| (idx, battery_chr) | {
+        match battery_chr.to_digit(10) {
+            Some(joltage) => Ok(joltage as u8),
+            None => Err(AocError::at(line, idx + 1, format!("Batteries must be single digits, but got '{}'", battery_chr))),
+        }
+    }).collect()
+}
 
-        return joltage_acc + get_bank_joltage(&bank_bytes[..], 2);
-    }).to_string()
+fn parse_banks (input: &str) -> AocResult<Vec<Vec<u8>>> {
+    input.lines()
+        .enumerate()
+        .map(| (line_num, bank) | parse_bank(line_num + 1, bank))
+        .collect()
 }
 
+#[allow(clippy::needless_return)]
+pub fn star_one (input: &str, params: &Params) -> AocResult<Answer> {
+    let Day3Params { batteries } = Day3Params::from(params, 2)?;
+    let banks = phase::parse(|| parse_banks(input))?;
+    let joltage = banks.iter().fold(0u64, | joltage_acc, bank_bytes | {
+        return joltage_acc + get_bank_joltage(&bank_bytes[..], batteries);
+    });
+    Ok(joltage.into())
+}
 
-pub fn star_two (input: String) -> String {
-    input.lines().fold(0u64, | joltage_acc, bank | {
-        let bank_bytes = bank.as_bytes().iter().map(| battery_chr | {
-            return battery_chr - b'0';
-        }).collect::<Vec<u8>>();
 
-        return joltage_acc + get_bank_joltage(&bank_bytes[..], 12);
-    }).to_string()
+#[allow(clippy::needless_return)]
+pub fn star_two (input: &str, params: &Params) -> AocResult<Answer> {
+    let Day3Params { batteries } = Day3Params::from(params, 12)?;
+    let banks = phase::parse(|| parse_banks(input))?;
+    let joltage = banks.iter().fold(0u64, | joltage_acc, bank_bytes | {
+        return joltage_acc + get_bank_joltage(&bank_bytes[..], batteries);
+    });
+    Ok(joltage.into())
 }
 
@@ -1,11 +1,18 @@
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::phase;
+use crate::solution::solution;
 
-struct Room {
-    tp_or_not_tp: Vec<bool>,
-    width: usize,
-    height: usize
+solution!(Day4, day = 4);
+
+pub struct Room {
+    pub tp_or_not_tp: Vec<bool>,
+    pub width: usize,
+    pub height: usize
 }
 
 impl std::fmt::Display for Room {
+    #[allow(clippy::writeln_empty_string)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for (spot, tp) in self.tp_or_not_tp.iter().enumerate() {
             if *tp {
@@ -25,7 +32,8 @@ impl std::fmt::Display for Room {
 }
 
 impl Room {
-    fn from (grid: String) -> Self {
+    #[allow(clippy::redundant_field_names)]
+    pub fn from (grid: &str) -> AocResult<Self> {
         let mut grid_width: Option<usize> = None;
         let mut width = 0;
 
@@ -33,18 +41,19 @@ impl Room {
 
         // Start at 1 because we start on the first line
         let mut height: usize = 1;
-        grid.chars().enumerate().for_each(| (spot, tp) | {
-            if tp == '\r' { return }
+        for tp in grid.chars() {
             if tp == '\n' {
                 if let Some(known_width) = grid_width {
-                    assert!(width == known_width, "Jagged grid detected!  Expected all rows to be of length {}, but row {} has {} columns", known_width, height, width);
+                    if width != known_width {
+                        return Err(AocError::on_line(height, format!("Jagged grid detected!  Expected all rows to be of length {}, but row {} has {} columns", known_width, height, width)));
+                    }
                 }
                 else {
                     grid_width = Some(width);
                 }
                 width = 0;
                 height += 1;
-                return;
+                continue;
             }
             width += 1;
 
@@ -55,32 +64,42 @@ impl Room {
                 tp_or_not_tp.push(true);
             }
             else {
-                panic!("The only character allowed in the grid are \\r, \\n, '@', and '.', but we got '{}' at index {}", tp, spot);
+                return Err(AocError::at(height, width, format!("The only character allowed in the grid are \\n, '@', and '.', but we got '{}'", tp)));
             }
-        });
+        }
 
-        if grid_width.is_none() {
-            panic!("Width of grid not found!  No newlines detected!");
+        let Some(grid_width) = grid_width else {
+            return Err(AocError::new("Width of grid not found!  No newlines detected!"));
+        };
+
+        // A trailing newline doesn't start another row, but any other last row has to be as wide as the rest
+        if width == 0 {
+            height -= 1;
+        }
+        else if width != grid_width {
+            return Err(AocError::on_line(height, format!("Jagged grid detected!  Expected all rows to be of length {}, but row {} has {} columns", grid_width, height, width)));
         }
 
-        Room {
+        Ok(Room {
             tp_or_not_tp: tp_or_not_tp,
-            width: grid_width.unwrap(),
+            width: grid_width,
             height: height
-        }
+        })
     }
 
-    fn get_index (&self, y: usize, x: usize) -> usize {
+    #[allow(clippy::needless_return)]
+    pub fn get_index (&self, y: usize, x: usize) -> usize {
         return y * self.width + x;
     }
 
-    fn get_yx (&self, spot: usize) -> (usize, usize) {
+    #[allow(clippy::needless_return)]
+    pub fn get_yx (&self, spot: usize) -> (usize, usize) {
         let y = spot / self.width;
         let x = spot % self.width;
         return ( y, x );
     }
 
-    fn get_neighbors (&self, spot: usize) -> [ Option<usize>; 8 ] {
+    pub fn get_neighbors (&self, spot: usize) -> [ Option<usize>; 8 ] {
 
         let ( y, x ) = self.get_yx(spot);
 
@@ -121,9 +140,9 @@ impl Room {
 
 
 
-pub fn star_one (input: String) -> String {
-    let tp_room = Room::from(input);
-    tp_room.tp_or_not_tp.iter().enumerate().fold(0usize, | accessible_acc, (spot, tp) | {
+pub fn star_one (input: &str) -> AocResult<Answer> {
+    let tp_room = phase::parse(|| Room::from(input))?;
+    Ok(tp_room.tp_or_not_tp.iter().enumerate().fold(0usize, | accessible_acc, (spot, tp) | {
         accessible_acc + if *tp {
             let neighbors = tp_room.get_neighbors(spot);
             let tp_neighbors: usize = neighbors.into_iter()
@@ -138,11 +157,12 @@ pub fn star_one (input: String) -> String {
             if tp_neighbors < 4 { 1 } else { 0 }
         }
         else { 0 }
-    }).to_string()
+    }).into())
 }
 
-pub fn star_two (input: String) -> String {
-    let mut tp_room = Room::from(input);
+#[allow(clippy::len_zero, clippy::needless_return)]
+pub fn star_two (input: &str) -> AocResult<Answer> {
+    let mut tp_room = phase::parse(|| Room::from(input))?;
     let mut removed_count = 0;
 
     loop {
@@ -175,6 +195,6 @@ pub fn star_two (input: String) -> String {
         }
         removed_count += removed.len();
     }
-    return removed_count.to_string();
+    return Ok(removed_count.into());
 }
 
@@ -1,31 +1,39 @@
 use std::{ops::RangeInclusive};
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::phase;
+use crate::solution::solution;
 
-struct StoreRoom {
-    freshness_ranges: Vec<RangeInclusive<u64>>,
-    ingredients: Vec<u64>
+solution!(Day5, day = 5);
+
+pub struct StoreRoom {
+    pub freshness_ranges: Vec<RangeInclusive<u64>>,
+    pub ingredients: Vec<u64>
 }
 
 impl StoreRoom {
 
-    fn eat_number (line: &str) -> (u64, &str) {
+    // Errors don't know where `line` starts, the caller adds the position
+    #[allow(clippy::needless_return, clippy::unnecessary_cast)]
+    fn eat_number (line: &str) -> AocResult<(u64, &str)> {
         let mut num: u64 = 0;
         let nonnum: Option<usize> = line.chars().enumerate().find_map(| (idx, chr) | {
-            if chr.is_numeric() {
-                let digit = chr.to_digit(10).unwrap();
+            if let Some(digit) = chr.to_digit(10) {
                 num = num * 10 + digit as u64;
                 return None;
             }
             return Some(idx);
         });
-        if let Some(idx) = nonnum {
-            return (num as u64, &line[idx..]);
-        }
-        else {
-            return (num as u64, &line[line.len()..]);
+        match (nonnum, line.chars().next()) {
+            (_, None) => return Err(AocError::new("Expected a number but the line ended")),
+            (Some(0), Some(chr)) => return Err(AocError::new(format!("Expected a number but got '{}'", chr))),
+            (Some(idx), _) => return Ok((num as u64, &line[idx..])),
+            (None, _) => return Ok((num as u64, &line[line.len()..])),
         }
     }
 
-    fn from (input: String) -> Self {
+    #[allow(clippy::len_zero)]
+    pub fn from (input: &str) -> AocResult<Self> {
         let mut lines = input.lines().peekable();
         let mut freshness_ranges: Vec<RangeInclusive<u64>> = Vec::new();
         let mut ingredients: Vec<u64> = Vec::new();
@@ -46,54 +54,77 @@ impl StoreRoom {
                 continue;
             }
 
-            let first_char = line.chars().next().expect("There must be a first character");
-            assert!(first_char.is_numeric(), "First character on every line must be a number but we got {} on line {} (line='{}')", first_char, line_idx, line);
+            // Column (1-based) of the start of whatever is left of the line
+            let column = | rest: &str | line.len() - rest.len() + 1;
+
+            let first_char = line.chars().next().expect("Empty lines are skipped above");
+            if !first_char.is_ascii_digit() {
+                return Err(AocError::at(line_idx, 1, format!("First character on every line must be a number but we got {}", first_char)));
+            }
 
             if getting_ranges {
-                let (start, next) = StoreRoom::eat_number(line);
-                assert!(next.len() > 0, "First part of range must be followed by second");
-                assert!(next.chars().next().unwrap() == '-', "First character after range start must be '-'");
-                let (end, empty) = StoreRoom::eat_number(&next[1..]);
-                assert!(empty.len() == 0, "Line must be empty after end range finishes");
+                let (start, next) = StoreRoom::eat_number(line)
+                    .map_err(| err | err.with_position(line_idx, column(line)))?;
+                if next.is_empty() {
+                    return Err(AocError::at(line_idx, column(next), "First part of range must be followed by second"));
+                }
+                if !next.starts_with('-') {
+                    return Err(AocError::at(line_idx, column(next), "First character after range start must be '-'"));
+                }
+                let (end, empty) = StoreRoom::eat_number(&next[1..])
+                    .map_err(| err | err.with_position(line_idx, column(&next[1..])))?;
+                if !empty.is_empty() {
+                    return Err(AocError::at(line_idx, column(empty), "Line must be empty after end range finishes"));
+                }
+                if end < start {
+                    return Err(AocError::at(line_idx, 1, format!("Range {}-{} ends before it starts", start, end)));
+                }
                 freshness_ranges.push(start..=end);
             }
             else {
-                let (ingredient, empty) = StoreRoom::eat_number(line);
-                assert!(empty.len() == 0, "Line must be empty after ingredient");
+                let (ingredient, empty) = StoreRoom::eat_number(line)
+                    .map_err(| err | err.with_position(line_idx, column(line)))?;
+                if !empty.is_empty() {
+                    return Err(AocError::at(line_idx, column(empty), "Line must be empty after ingredient"));
+                }
                 ingredients.push(ingredient);
             }
         }
 
-        StoreRoom { freshness_ranges , ingredients }
+        Ok(StoreRoom { freshness_ranges , ingredients })
     }
 }
 
 
 
-pub fn star_one (input: String) -> String {
+#[allow(clippy::needless_return)]
+pub fn star_one (input: &str) -> AocResult<Answer> {
     let StoreRoom {
         ingredients,
         freshness_ranges
-    } = StoreRoom::from(input);
+    } = phase::parse(|| StoreRoom::from(input))?;
 
-    ingredients.into_iter().fold(0usize, | fresh_acc, ingredient | {
+    Ok(ingredients.into_iter().fold(0usize, | fresh_acc, ingredient | {
         let fresh = freshness_ranges.iter().any(| range | {
             range.contains(&ingredient)
         });
         return fresh_acc + if fresh { 1 } else { 0 }
-    }).to_string()
+    }).into())
 }
 
-pub fn star_two (input: String) -> String {
+#[allow(clippy::needless_borrow, clippy::needless_return)]
+pub fn star_two (input: &str) -> AocResult<Answer> {
     let StoreRoom {
         ingredients: _ingredients,
         mut freshness_ranges
-    } = StoreRoom::from(input);
+    } = phase::parse(|| StoreRoom::from(input))?;
 
     freshness_ranges.sort_by(| range_a, range_b | range_a.start().cmp(&range_b.start()));
 
     let mut freshness_iterator = freshness_ranges.into_iter();
-    let first = freshness_iterator.next().unwrap();
+    let Some(first) = freshness_iterator.next() else {
+        return Err(AocError::new("There must be at least one freshness range"));
+    };
 
     let merged_ranges= freshness_iterator.fold(vec![ first ], | mut acc, range | {
         // Should always have a last element
@@ -112,7 +143,7 @@ pub fn star_two (input: String) -> String {
         return acc;
     });
 
-    merged_ranges.iter().fold(0u128, | total_acc, merged_range | {
+    Ok(merged_ranges.iter().fold(0u128, | total_acc, merged_range | {
         total_acc + (merged_range.end() - merged_range.start()) as u128 + 1
-    }).to_string()
+    }).into())
 }
\ No newline at end of file
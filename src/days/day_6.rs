@@ -1,20 +1,26 @@
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::phase;
+use crate::solution::solution;
+
+solution!(Day6, day = 6);
 
 #[derive(Copy, Clone)]
-enum Op {
+pub enum Op {
     Mult,
     Add
 }
 
 impl Op {
-    fn from (chr: char) -> Self {
+    pub fn from (chr: char) -> AocResult<Self> {
         if chr == '*' {
-            Self::Mult
+            Ok(Self::Mult)
         }
         else if chr == '+' {
-            Self::Add
+            Ok(Self::Add)
         }
         else {
-            panic!("The only acceptable characters for operations are '*' and '+'!");
+            Err(AocError::new(format!("The only acceptable characters for operations are '*' and '+', but we got '{}'!", chr)))
         }
     }
 }
@@ -28,13 +34,13 @@ impl std::fmt::Display for Op {
     }
 }
 
-struct Column {
-    values: Vec<u128>,
-    operation: Op,
+pub struct Column {
+    pub values: Vec<u128>,
+    pub operation: Op,
 }
 
 impl Column {
-    fn solve (&self) -> u128 {
+    pub fn solve (&self) -> u128 {
         let mut solve: u128 = match self.operation {
             Op::Mult => 1,
             Op::Add => 0,
@@ -63,22 +69,22 @@ impl std::fmt::Display for Column {
     }
 }
 
-enum Worksheet {
+pub enum Worksheet {
     Human ( HumanWorksheet ),
     Cephalopod ( CephalopodWorksheet )
 }
 
 
-struct HumanWorksheet {
-    columns: Vec<Column>
+pub struct HumanWorksheet {
+    pub columns: Vec<Column>
 }
 
-struct CephalopodWorksheet {
-    columns: Vec<Column>
+pub struct CephalopodWorksheet {
+    pub columns: Vec<Column>
 }
 
 impl Worksheet {
-    fn solve (&self) -> u128 {
+    pub fn solve (&self) -> u128 {
         let columns = match self {
             Worksheet::Human(human_worksheet) => {
                 &human_worksheet.columns
@@ -95,6 +101,7 @@ impl Worksheet {
 }
 
 impl HumanWorksheet {
+    #[allow(clippy::needless_return)]
     fn eat_number_skip_whitespace (line: &str) -> (Option<u128>, &str) {
         let mut num: Option<u128> = None;
         let nonnum: Option<usize> = line.chars().enumerate().find_map(| (idx, chr) | {
@@ -117,6 +124,7 @@ impl HumanWorksheet {
         }
     }
 
+    #[allow(clippy::needless_return)]
     fn eat_next_char_skip_whitespace (line: &str) -> (Option<char>, &str) {
         let mut opt_chr: Option<char> = None;
         let non_ws: Option<usize> = line.chars().enumerate().find_map(| (idx, chr) | {
@@ -135,7 +143,8 @@ impl HumanWorksheet {
         }
     }
 
-    fn from (input: String) -> Self {
+    #[allow(clippy::collapsible_if, clippy::len_zero, clippy::needless_range_loop, clippy::redundant_field_names)]
+    pub fn from (input: &str) -> AocResult<Self> {
         
         let mut ops: Vec<Op> = Vec::new();
         let mut numbers: Vec<u128> = Vec::new();
@@ -151,7 +160,9 @@ impl HumanWorksheet {
                 if line_slice.contains('+') || line_slice.contains('*') {
                     let (chr, slice) = HumanWorksheet::eat_next_char_skip_whitespace(line_slice);
                     if let Some(chr) = chr {
-                        ops.push(Op::from(chr));
+                        // `slice` starts right after `chr`, so this is `chr`'s 1-based column
+                        let column = line.len() - slice.len();
+                        ops.push(Op::from(chr).map_err(| err | err.with_position(line_index + 1, column))?);
                         width += 1;
                     }
                     line_slice = slice;
@@ -162,13 +173,17 @@ impl HumanWorksheet {
                         numbers.push(num);
                         width += 1;
                     }
+                    else if slice.len() == line_slice.len() {
+                        // Didn't eat anything, so this isn't a number or whitespace
+                        return Err(AocError::at(line_index + 1, line.len() - slice.len() + 1, "Expected a number, '+', or '*'"));
+                    }
                     line_slice = slice;
                 }
             }
             
             if let Some(last_width) = last_width {
                 if last_width != width {
-                    panic!("Jagged worksheet detected! Line {} has {} elements, but the previous line had {}", line_index + 1, width, last_width);
+                    return Err(AocError::on_line(line_index + 1, format!("Jagged worksheet detected! Line {} has {} elements, but the previous line had {}", line_index + 1, width, last_width)));
                 }
             }
             last_width = Some(width);
@@ -176,17 +191,19 @@ impl HumanWorksheet {
 
         }
 
-        if last_width.is_none() {
-            panic!("Width of worksheet couldn't be found!");
+        let Some(width) = last_width else {
+            return Err(AocError::new("Width of worksheet couldn't be found!"));
+        };
+
+        if ops.len() != width {
+            return Err(AocError::new(format!("Expected a line of {} operations at the bottom of the worksheet, but found {}", width, ops.len())));
         }
 
-        
-        let width = last_width.unwrap();
         let mut columns: Vec<Column> = Vec::with_capacity(width);
 
         for col in 0..width {
-            let op = ops.get(col).unwrap();
-            columns.push(Column { values: Vec::new(), operation: *op });
+            let op = ops[col];
+            columns.push(Column { values: Vec::new(), operation: op });
         }
 
         for (idx, num) in numbers.iter().enumerate() {
@@ -195,9 +212,9 @@ impl HumanWorksheet {
             col.values.push(*num);
         }
 
-        Self {
+        Ok(Self {
             columns: columns
-        }
+        })
     }
 }
 
@@ -218,9 +235,10 @@ impl std::fmt::Display for Worksheet {
 
 
 impl CephalopodWorksheet {
-    fn from(input: String) -> Self {
+    #[allow(clippy::len_zero)]
+    pub fn from(input: &str) -> AocResult<Self> {
 
-        let lines: Vec<String> = input.lines().map(String::from).collect();
+        let lines: Vec<&str> = input.lines().collect();
 
         let mut op_stack: Vec<Op> = Vec::new();
 
@@ -228,22 +246,23 @@ impl CephalopodWorksheet {
         let mut prev = 0;
 
         let mut last_idx = 0;
-        let last_line = lines.last().unwrap();
-        let mut nums_per_column: Vec<usize> = last_line.chars().enumerate().filter_map(| (idx, chr) | {
+        let Some(last_line) = lines.last() else {
+            return Err(AocError::new("The worksheet is empty!"));
+        };
+        let op_line = lines.len();
+        let mut nums_per_column: Vec<usize> = Vec::new();
+        for (idx, chr) in last_line.chars().enumerate() {
             if first { 
-                op_stack.push(Op::from(chr));
+                op_stack.push(Op::from(chr).map_err(| err | err.with_position(op_line, idx + 1))?);
                 first = false;
-                None 
             }
             else if chr == '+' || chr == '*' {
                 last_idx = idx;
-                op_stack.push(Op::from(chr));
-                let ret = idx - 1 - prev;
+                op_stack.push(Op::from(chr)?);
+                nums_per_column.push(idx - 1 - prev);
                 prev = idx;
-                Some(ret)
             }
-            else { None }
-        }).collect();
+        }
 
         
         // Never added the last column in the loop above, but we know it
@@ -256,14 +275,16 @@ impl CephalopodWorksheet {
         'outer: loop {
             
             for y in 0..lines.len() {
-                let row = lines.get(y).unwrap()[..].as_bytes();
-                let x = row.len() - 1 - x_back;
+                let row = lines[y].as_bytes();
+                let Some(x) = row.len().checked_sub(1 + x_back) else {
+                    return Err(AocError::on_line(y + 1, format!("Jagged worksheet detected! Line {} is shorter than the operations line", y + 1)));
+                };
 
                 let chr = row[x];
                 if chr == b'*' ||  chr == b'+' || y == lines.len() - 1 {
 
                     if chr == b'+' || chr == b'*' {
-                        let c= Column { values: col.clone(), operation: Op::from(chr as char) };
+                        let c= Column { values: col.clone(), operation: Op::from(chr as char)? };
                         cols.push(c);
                         
                         if nums_per_column.len() == 0 {
@@ -284,7 +305,12 @@ impl CephalopodWorksheet {
                     continue;
                 }
                 else {
-                    let num = col.get_mut(num_index).expect("Catastrophic failure!!!");
+                    let Some(num) = col.get_mut(num_index) else {
+                        return Err(AocError::at(y + 1, x + 1, "More numbers in this column than there is room for under its operation"));
+                    };
+                    if !chr.is_ascii_digit() {
+                        return Err(AocError::at(y + 1, x + 1, format!("Expected a digit, but got '{}'", chr as char)));
+                    }
                     let digit = chr - b'0';
                     *num = *num * 10 + digit as u128;
                 }
@@ -332,20 +358,28 @@ impl CephalopodWorksheet {
 
         // CephalopodWorksheet { columns: ceph_columns }
 
-        CephalopodWorksheet { columns: cols }
+        Ok(CephalopodWorksheet { columns: cols })
     }
 }
 
 
-pub fn star_one (input: String) -> String {
-    let human_ws = HumanWorksheet::from(input);
+pub fn star_one (input: &str) -> AocResult<Answer> {
+    let human_ws = phase::parse(|| HumanWorksheet::from(input))?;
     let ws = Worksheet::Human(human_ws);
-    ws.solve().to_string()
+    Ok(ws.solve().into())
 }
 
-pub fn star_two (input: String) -> String {
-    let cephalopod_ws = CephalopodWorksheet::from(input);
+pub fn star_two (input: &str) -> AocResult<Answer> {
+    let cephalopod_ws = phase::parse(|| CephalopodWorksheet::from(input))?;
     let ws = Worksheet::Cephalopod(cephalopod_ws);
-    ws.solve().to_string()
+    Ok(ws.solve().into())
 }
 
+// For report appendices, every column of the worksheet and what it works out to, read both ways
+pub fn render_human_worksheet (input: &str) -> AocResult<String> {
+    Ok(Worksheet::Human(HumanWorksheet::from(input)?).to_string())
+}
+
+pub fn render_cephalopod_worksheet (input: &str) -> AocResult<String> {
+    Ok(Worksheet::Cephalopod(CephalopodWorksheet::from(input)?).to_string())
+}
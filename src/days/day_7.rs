@@ -1,8 +1,15 @@
 use std::{collections::HashSet, rc::Rc};
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::log::trace;
+use crate::phase;
+use crate::solution::solution;
 
+solution!(Day7, day = 7);
+solution!(Day7Naive, day = 7, name = "naive", star_one = star_one, star_two = star_two_naive);
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct RowCol (usize, usize);
+pub struct RowCol (pub usize, pub usize);
 
 impl std::fmt::Display for RowCol {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -25,30 +32,30 @@ impl RowCol {
     }
 }
 
-struct TachyonManifold {
-    splitters: HashSet<RowCol>,
-    tachyons: HashSet<RowCol>,
-    width: usize,
-    height: usize,
+pub struct TachyonManifold {
+    pub splitters: HashSet<RowCol>,
+    pub tachyons: HashSet<RowCol>,
+    pub width: usize,
+    pub height: usize,
 }
 
 
 impl TachyonManifold {
-    fn from (input: String) -> Self {
+    pub fn from (input: &str) -> AocResult<Self> {
         let mut width: Option<usize> = None;
         let mut start: Option<RowCol> = None;
-        let (total_chars, splitters) = input.chars().fold((0, HashSet::<RowCol>::new()), | (char_num, mut splitters), chr | {
-            if chr == '\n' || chr == '\r' {
+        let (total_chars, splitters) = input.chars().try_fold((0, HashSet::<RowCol>::new()), | (char_num, mut splitters), chr | {
+            if chr == '\n' {
                 if width.is_none() {
                     width = Some(char_num);
                 }
-                return (char_num, splitters);
+                return Ok((char_num, splitters));
             }
 
             if chr == 'S' {
                 let pos= RowCol::from_optional_width(char_num, width);
                 if let Some(start) = &start {
-                    panic!("Multiple starting spots found! There is an 'S' {} (character {}), but we previously found one at {}", pos, char_num, start)
+                    return Err(AocError::at(pos.0 + 1, pos.1 + 1, format!("Multiple starting spots found! There is an 'S' {} (character {}), but we previously found one at {}", pos, char_num, start)));
                 }
                 start = Some(pos);
             }
@@ -57,30 +64,35 @@ impl TachyonManifold {
                 splitters.insert(RowCol::from_optional_width(char_num, width));
             }
 
-            (char_num + 1, splitters)
-        });
+            Ok((char_num + 1, splitters))
+        })?;
 
         // Unpack the start coordinate and insert it into a hash set (because the splitters will cause new beams to appear)
         let tachyons = if let Some(start) = start {
             HashSet::<RowCol>::from([ start ])
         }
         else {
-            panic!("Could not find the starting position of the first Tachyon!  No 'S' characters were found!");
+            return Err(AocError::new("Could not find the starting position of the first Tachyon!  No 'S' characters were found!"));
         };
 
         let width = if let Some(width) = width {
             width
         }
         else {
-            panic!("Could not calculate the width of the TachyonManifold! This is likely because there were no newlines (\\n) or carriage returns (\\n) in the input!");
+            return Err(AocError::new("Could not calculate the width of the TachyonManifold! This is likely because there were no newlines (\\n) in the input!"));
         };
 
+        if width == 0 {
+            return Err(AocError::on_line(1, "The first row of the TachyonManifold is empty!"));
+        }
+
         // Grid must be equal columns per row
         let height = total_chars / width;
-        TachyonManifold { splitters, tachyons, width, height }
+        Ok(TachyonManifold { splitters, tachyons, width, height })
     }
 
-    fn step (self) -> (Self, usize, bool) {
+    #[allow(clippy::redundant_field_names)]
+    pub fn step (self) -> (Self, usize, bool) {
         let TachyonManifold {
             splitters,
             tachyons,
@@ -130,6 +142,7 @@ impl TachyonManifold {
 }
 
 impl std::fmt::Display for TachyonManifold {
+    #[allow(clippy::writeln_empty_string)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for row in 0..self.height {
             for col in 0..self.width {
@@ -151,9 +164,26 @@ impl std::fmt::Display for TachyonManifold {
     }
 }
 
-pub fn star_one (input: String) -> String {
+// For report appendices, the manifold with every spot a beam passed through on its way to the bottom
+pub fn render_manifold (input: &str) -> AocResult<String> {
+    let mut tachyon_manifold = phase::parse(|| TachyonManifold::from(input))?;
+    let mut beams = tachyon_manifold.tachyons.clone();
+    loop {
+        let (ntm, _, can_continue) = tachyon_manifold.step();
+        tachyon_manifold = ntm;
+        beams.extend(tachyon_manifold.tachyons.iter().cloned());
+
+        if !can_continue {
+            break;
+        }
+    }
+    tachyon_manifold.tachyons = beams;
+    Ok(tachyon_manifold.to_string())
+}
+
+pub fn star_one (input: &str) -> AocResult<Answer> {
     let mut split_count = 0;
-    let mut tachyon_manifold = TachyonManifold::from(input);
+    let mut tachyon_manifold = phase::parse(|| TachyonManifold::from(input))?;
     loop {
         let (ntm, splits, can_continue) = tachyon_manifold.step();
         tachyon_manifold = ntm;
@@ -163,7 +193,7 @@ pub fn star_one (input: String) -> String {
             break;
         }
     }
-    split_count.to_string()
+    Ok(split_count.into())
 }
 
 
@@ -198,6 +228,7 @@ impl QuantumTachyonManifold {
         QuantumTachyonManifold { splitters: splitters_ref, tachyon, width, height }
     }
 
+    #[allow(clippy::redundant_field_names)]
     fn step_quantumly (self) -> CatInABox {
 
         let QuantumTachyonManifold {
@@ -251,9 +282,10 @@ impl QuantumTachyonManifold {
 }
 
 // Totally made this naive implementation on purpose to show what a less experienced programmer would do
-fn _star_two_naive (input: String) -> String {
+#[allow(clippy::bind_instead_of_map, clippy::len_zero, clippy::unit_arg)]
+pub fn star_two_naive (input: &str) -> AocResult<Answer> {
     let mut parallel_universes = 1;
-    let root_universe = TachyonManifold::from(input);
+    let root_universe = phase::parse(|| TachyonManifold::from(input))?;
     let root_universe_quantum = QuantumTachyonManifold::from(root_universe);
     let mut current_universes = vec![ root_universe_quantum ];
 
@@ -262,10 +294,13 @@ fn _star_two_naive (input: String) -> String {
             break;
         }
 
-        println!("Current universes: {}", current_universes.len());
-        println!("Row = {}", current_universes.get(0).unwrap().tachyon.0);
-        println!("Height = {}", current_universes.get(0).unwrap().height);
-        println!("Mem = {} GB", (std::mem::size_of::<QuantumTachyonManifold>() * current_universes.capacity()) as f64 / (1024f64 * 1024f64 * 1024f64));
+        trace!(
+            "{} universes on row {} of {} ({} GB)",
+            current_universes.len(),
+            current_universes.first().unwrap().tachyon.0,
+            current_universes.first().unwrap().height,
+            (std::mem::size_of::<QuantumTachyonManifold>() * current_universes.capacity()) as f64 / (1024f64 * 1024f64 * 1024f64)
+        );
     
         let mut step_universes: Vec<QuantumTachyonManifold> = Vec::new();
         for tachyon_manifold in current_universes {
@@ -282,11 +317,11 @@ fn _star_two_naive (input: String) -> String {
         }
         current_universes = step_universes;
     }
-    parallel_universes.to_string()
+    Ok(parallel_universes.into())
 }
 
 
-struct EfficientQuantumTachyonManifold {
+pub struct EfficientQuantumTachyonManifold {
     splitters: Vec<HashSet<usize>>,
     initial_tachyon: RowCol,
     width: usize,
@@ -294,7 +329,7 @@ struct EfficientQuantumTachyonManifold {
 }
 
 impl EfficientQuantumTachyonManifold {
-    fn from (binary_tachyon_manifold: TachyonManifold) -> Self {
+    pub fn from (binary_tachyon_manifold: TachyonManifold) -> Self {
         let TachyonManifold {
             splitters,
             tachyons,
@@ -317,7 +352,8 @@ impl EfficientQuantumTachyonManifold {
         EfficientQuantumTachyonManifold { splitters: rowise_splitters, initial_tachyon: tachyon, width, height }
     }
 
-    fn step_to_bottom_quantumly (self) -> usize {
+    #[allow(clippy::needless_range_loop)]
+    pub fn step_to_bottom_quantumly (self) -> usize {
         let EfficientQuantumTachyonManifold {
             splitters,
             initial_tachyon,
@@ -383,8 +419,8 @@ impl EfficientQuantumTachyonManifold {
     }
 }
 
-pub fn star_two (input: String) -> String {
-    let binary_tachyon_manifold = TachyonManifold::from(input);
+pub fn star_two (input: &str) -> AocResult<Answer> {
+    let binary_tachyon_manifold = phase::parse(|| TachyonManifold::from(input))?;
     let efficient_quantum_tachyon_manifold = EfficientQuantumTachyonManifold::from(binary_tachyon_manifold);
-    efficient_quantum_tachyon_manifold.step_to_bottom_quantumly().to_string()
+    Ok(efficient_quantum_tachyon_manifold.step_to_bottom_quantumly().into())
 }
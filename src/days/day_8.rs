@@ -1,5 +1,32 @@
 use std::{cmp::Ordering, collections::{HashSet}, fmt::Debug};
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::log::debug;
+use crate::params::Params;
+use crate::phase;
+use crate::solution::solution;
+
+solution!(Day8, day = 8, params);
+
+// `--param connections=N`, how many connections star one makes before multiplying the biggest circuits
+// The example stops at the 10th connection and the real input at the 1000th
+pub struct Day8Params {
+    pub connections: usize,
+}
+
+impl Day8Params {
+    pub fn from (params: &Params) -> AocResult<Self> {
+        params.expect_keys(&[ "connections" ])?;
+        let connections = params.get("connections", if params.example() { 10 } else { 1000 })?;
+
+        if connections == 0 {
+            return Err(AocError::new("connections has to be at least 1"));
+        }
+        Ok(Day8Params { connections })
+    }
+}
 
+#[allow(clippy::needless_lifetimes, clippy::needless_return)]
 fn eat_number_skip_charset <'a, 'b> (line: &'a str, skip_charset: &'b str) -> (Option<usize>, &'a str) {
     let mut num: Option<usize> = None;
     let nonnum: Option<usize> = line.chars().enumerate().find_map(| (idx, chr) | {
@@ -23,29 +50,30 @@ fn eat_number_skip_charset <'a, 'b> (line: &'a str, skip_charset: &'b str) -> (O
 }
 
 #[derive(Debug, Copy, Clone)]
-struct Point (usize, usize, usize);
+pub struct Point (pub usize, pub usize, pub usize);
 
 impl Point {
-    fn extract_point (line: &str) -> (Self, &str) {
+    #[allow(clippy::needless_return)]
+    pub fn extract_point (line: &str) -> AocResult<(Self, &str)> {
         let skipme = ",\n\r\t ";
         let (first, line) = eat_number_skip_charset(line, skipme);
         let (second, line) = eat_number_skip_charset(line, skipme);
         let (third, line) = eat_number_skip_charset(line, skipme);
 
-        if first.is_none() || second.is_none() || third.is_none() {
-            panic!("Could not parse point from input line '{line}'");
-        }
+        let (Some(first), Some(second), Some(third)) = (first, second, third) else {
+            return Err(AocError::new("Could not parse point, expected three comma separated numbers"));
+        };
         
         let pt = Point (
-            first.unwrap(),
-            second.unwrap(),
-            third.unwrap()
+            first,
+            second,
+            third
         );
 
-        return (pt, line);
+        return Ok((pt, line));
     }
 
-    fn dist (&self, other: &Point) -> f64 {
+    pub fn dist (&self, other: &Point) -> f64 {
         let Point(sx, sy, sz) = *self;
         let Point(ox, oy, oz) = *other;
 
@@ -66,13 +94,19 @@ impl std::fmt::Display for Point {
     }
 }
 
-#[derive(PartialEq, PartialOrd, Clone, Copy, Debug)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 struct DistanceEntry (usize, usize, f64);
 
 impl Eq for DistanceEntry {
 
 }
 
+impl PartialOrd for DistanceEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Ord for DistanceEntry {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.2.total_cmp(&other.2)
@@ -102,6 +136,7 @@ impl <T: Ord + Copy + Clone + Debug> DistanceQueue <T> {
         (idx * 2 + 1, idx * 2 + 2)
     }
 
+    #[allow(clippy::manual_swap)]
     fn swap_idx (&mut self, idx_1: usize, idx_2: usize) {
         let tmp = self.queue[idx_1];
         self.queue[idx_1] = self.queue[idx_2];
@@ -125,6 +160,7 @@ impl <T: Ord + Copy + Clone + Debug> DistanceQueue <T> {
         entry_idx
     }
 
+    #[allow(clippy::len_zero)]
     fn peek (&self) -> Option<&T> {
         if self.queue.len() == 0 {
             None
@@ -134,6 +170,8 @@ impl <T: Ord + Copy + Clone + Debug> DistanceQueue <T> {
         }
     }
 
+    // depth only goes down the recursion, it's there to look at in a debugger
+    #[allow(clippy::only_used_in_recursion)]
     fn confirm_tree (&self, idx: usize, depth: usize) -> bool {
         if idx >= self.queue.len() {
             return true;
@@ -166,10 +204,10 @@ impl <T: Ord + Copy + Clone + Debug> DistanceQueue <T> {
         let confirm = c1 && c2 && c3 && c4;
 
         if !confirm {
-            println!("self.queue[{idx}] ({:?}) <= self.queue[{left}] ({:?}): {}", get(idx), get(left), c1);
-            println!("self.queue[{idx}] ({:?}) <= self.queue[{right}] ({:?}): {}", get(idx), get(right), c2);
-            println!("self.confirm_tree({left}):  {}", self.confirm_tree(left, depth+1));
-            println!("self.confirm_tree({right}): {}", self.confirm_tree(right, depth+1));
+            debug!("self.queue[{idx}] ({:?}) <= self.queue[{left}] ({:?}): {}", get(idx), get(left), c1);
+            debug!("self.queue[{idx}] ({:?}) <= self.queue[{right}] ({:?}): {}", get(idx), get(right), c2);
+            debug!("self.confirm_tree({left}):  {}", self.confirm_tree(left, depth+1));
+            debug!("self.confirm_tree({right}): {}", self.confirm_tree(right, depth+1));
         }
 
         assert!(
@@ -284,47 +322,78 @@ impl <T: Ord + Copy + Clone + Debug> DistanceQueue <T> {
 
 
 #[derive(Debug)]
-enum CircuitState {
+pub enum CircuitState {
     Alive,
     Dead
 }
 
 #[derive(Debug)]
 #[allow(unused)]
-struct Circuit {
-    state: CircuitState, 
-    id: usize,
-    point_indexes: HashSet<usize>,
-    connections: usize,
+pub struct Circuit {
+    pub state: CircuitState, 
+    pub id: usize,
+    pub point_indexes: HashSet<usize>,
+    pub connections: usize,
 }
 
-type CircuitIndex = usize;
+pub type CircuitIndex = usize;
 
 #[allow(unused)]
-struct Graph {
-    points: Vec<Point>,
+pub struct Graph {
+    pub points: Vec<Point>,
 
     // Indexed by index of p1, then index of p2, result is distance between p1 and p2
     // distance_matrix[idx_p1][pdx_p2] = d(p1, p2)
-    distance_matrix: Vec<Vec<f64>>,
+    pub distance_matrix: Vec<Vec<f64>>,
 
     ordered_distances: DistanceQueue<DistanceEntry>,
 
-    circuits: Vec<Circuit>,
-    circuits_map: Vec<CircuitIndex>
+    pub circuits: Vec<Circuit>,
+    pub circuits_map: Vec<CircuitIndex>
 }
 
 impl Graph {
-    fn from (input: String) -> Self {
-        let mut points: Vec<Point> = Vec::new();
-        
-        let mut input = &input[..];
-        while input.len() > 0 {
-            let (point, next_input) = Point::extract_point(input);
-            points.push(point);
-            input = next_input;
+    pub fn from (input: &str) -> AocResult<Self> {
+        let points = phase::phase("points", || {
+            let mut points: Vec<Point> = Vec::new();
+
+            // One point per line, so a point that's missing a coordinate can't eat into the next line
+            for (line_idx, line) in input.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                let (point, rest) = Point::extract_point(line).map_err(| err | err.with_line(line_idx + 1))?;
+                if !rest.trim().is_empty() {
+                    let column = line.len() - rest.len() + 1;
+                    return Err(AocError::at(line_idx + 1, column, "Unexpected characters after point"));
+                }
+                points.push(point);
+            }
+            Ok(points)
+        })?;
+
+        // Every pair of points goes into the heap, which is where almost all of the parse time goes
+        let (distance_matrix, dqueue) = phase::phase("distances", || Graph::distances(&points));
+
+        let mut circuits: Vec<Circuit> = Vec::new();
+        let mut circuits_map: Vec<CircuitIndex> = Vec::new();
+        for pidx in 0..points.len() {
+            circuits.push(Circuit { 
+                state: CircuitState::Alive, 
+                id: pidx, 
+                point_indexes: HashSet::from([ pidx ]), 
+                connections: 0 
+            });
+            circuits_map.push(pidx);
         }
 
+        Ok(Graph { points, distance_matrix, circuits, circuits_map, ordered_distances: dqueue })
+    }
+
+    // Distances between every pair of points, as a matrix and as a queue of the closest pairs first
+    #[allow(clippy::needless_range_loop)]
+    fn distances (points: &[Point]) -> (Vec<Vec<f64>>, DistanceQueue<DistanceEntry>) {
         let point_count = points.len();
         let mut dqueue = DistanceQueue::<DistanceEntry>::with_capacity(
             point_count * point_count
@@ -354,22 +423,10 @@ impl Graph {
             }
             distance_matrix.push(p1_distances);
         }
-
-        let mut circuits: Vec<Circuit> = Vec::new();
-        let mut circuits_map: Vec<CircuitIndex> = Vec::new();
-        for pidx in 0..points.len() {
-            circuits.push(Circuit { 
-                state: CircuitState::Alive, 
-                id: pidx, 
-                point_indexes: HashSet::from([ pidx ]), 
-                connections: 0 
-            });
-            circuits_map.push(pidx);
-        }
-
-        Graph { points, distance_matrix, circuits, circuits_map, ordered_distances: dqueue }
+        (distance_matrix, dqueue)
     }
 
+    #[allow(clippy::match_like_matches_macro, clippy::unnecessary_sort_by)]
     fn _print_circuits (&self, threshold: usize) {
         
         let mut c = self.circuits.iter().filter(| circ | {
@@ -386,19 +443,17 @@ impl Graph {
             if circ.connections < threshold {
                 break;
             }
-            print!("Circuit (cxn={}) [{}] [", circ.connections, if let CircuitState::Alive = circ.state {
+            let points: String = circ.point_indexes.iter().map(| point | format!("{}, ", point)).collect();
+            debug!("Circuit (cxn={}) [{}] [{}]", circ.connections, if let CircuitState::Alive = circ.state {
                 "ALIVE"
             } else {
                 "DEAD"
-            });
-            for point in &circ.point_indexes {
-                print!("{}, ", point);
-            }
-            println!("]");
+            }, points);
         }
     }
 
-    fn add_connection (&mut self) -> Option<(usize, usize)> {
+    #[allow(clippy::clone_on_copy, clippy::needless_return, clippy::question_mark)]
+    pub fn add_connection (&mut self) -> Option<(usize, usize)> {
         let connection = self.ordered_distances.dequeue();
         let DistanceEntry( p1_idx, p2_idx, _distance ) = if let Some(connection) = connection {
             connection
@@ -445,11 +500,15 @@ impl Graph {
 }
 
 
-pub fn star_one (input: String) -> String {
+#[allow(clippy::match_like_matches_macro, clippy::needless_borrow)]
+pub fn star_one (input: &str, params: &Params) -> AocResult<Answer> {
+    let Day8Params { connections } = Day8Params::from(params)?;
 
-    let mut graph = Graph::from(input);
+    let mut graph = phase::parse(|| Graph::from(input))?;
     loop {
-        graph.add_connection();
+        if graph.add_connection().is_none() {
+            return Err(AocError::new(format!("Ran out of connections between the {} points before making {} of them! (the number of connections can be changed with --param connections=N)", graph.points.len(), connections)));
+        }
         
         // Add all the connections of the living circuits
         let cxns = graph.circuits.iter().filter(| circ | {
@@ -465,18 +524,8 @@ pub fn star_one (input: String) -> String {
         })
         .sum::<usize>();
 
-        // Example only has 20 nodes, and also only wants you to stop at the 
-        //      10th connection
-        if graph.points.len() == 20 {
-            if cxns == 10 {
-                break;
-            }
-        }
-        // Real input has 1000 notes and stops at 1000 connections
-        else {
-            if cxns == 1000 {
-                break;
-            }
+        if cxns == connections {
+            break;
         }
 
     }
@@ -497,17 +546,19 @@ pub fn star_one (input: String) -> String {
         .take(3)
         .product::<usize>();
 
-    product.to_string()
+    Ok(product.into())
 }
 
-pub fn star_two (input: String) -> String {
-    
-    let mut graph = Graph::from(input);
+// Star two keeps connecting until everything is one circuit, `connections` doesn't apply
+#[allow(clippy::match_like_matches_macro)]
+pub fn star_two (input: &str, params: &Params) -> AocResult<Answer> {
+    Day8Params::from(params)?;
+
+    let mut graph = phase::parse(|| Graph::from(input))?;
     loop {
-        let latest_connection = graph.add_connection();
-        if latest_connection.is_none() {
-            panic!("Couldn't find another connection!");
-        }
+        let Some(latest_connection) = graph.add_connection() else {
+            return Err(AocError::new("Couldn't find another connection!"));
+        };
 
         // Get the count of living circuits
         let living_circuits = graph.circuits.iter().filter(| circ | {
@@ -522,10 +573,10 @@ pub fn star_two (input: String) -> String {
         // If there is only one living circuit, then take the recently joined pair of points
         //      and multiply their x-coords for the result
         if living_circuits == 1 {
-            let (p1_idx, p2_idx) = latest_connection.unwrap();
+            let (p1_idx, p2_idx) = latest_connection;
             let Point(p1_x, _p1_y, _p1_z) = graph.points[p1_idx];
             let Point(p2_x, _p2_y, _p2_z) = graph.points[p2_idx];
-            return (p1_x * p2_x).to_string();
+            return Ok((p1_x * p2_x).into());
         }
     }
 }
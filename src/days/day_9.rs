@@ -1,9 +1,18 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
-use owo_colors::{OwoColorize, colors::xterm::PigmentIndigo, colors::*};
+use std::collections::{HashMap, HashSet};
+use owo_colors::{OwoColorize, colors::*};
+use crate::answer::Answer;
+use crate::error::{AocError, AocResult};
+use crate::log::{debug, trace};
+use crate::phase;
+use crate::solution::solution;
+
+solution!(Day9, day = 9);
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point(usize, usize);
+pub struct Point(pub usize, pub usize);
 
+// Only filled in for now, nothing reads it back yet
+#[allow(dead_code)]
 struct GreenPoint {
     r1: Point,
     r2: Point,
@@ -12,11 +21,19 @@ struct GreenPoint {
 }
 
 impl Point {
-    fn from (line: &str) -> Self {
-        let mut xy = line.split(",");
-        let x = usize::from_str_radix(xy.next().unwrap(), 10).unwrap();
-        let y = usize::from_str_radix(xy.next().unwrap(), 10).unwrap();
-        Point(x, y)
+    // Errors only carry the column, the caller knows which line this was
+    pub fn from (line: &str) -> AocResult<Self> {
+        let Some((x, y)) = line.split_once(",") else {
+            return Err(AocError::new("Expected a point in the form 'x,y'"));
+        };
+        let x = x.parse::<usize>().map_err(| err | {
+            AocError::new(format!("Could not parse x coordinate '{}': {}", x, err)).with_column(1)
+        })?;
+        let y_column = line.len() - y.len() + 1;
+        let y = y.parse::<usize>().map_err(| err | {
+            AocError::new(format!("Could not parse y coordinate '{}': {}", y, err)).with_column(y_column)
+        })?;
+        Ok(Point(x, y))
     }
 
     fn shares_a_line_with (&self, other: &Point) -> bool {
@@ -24,18 +41,21 @@ impl Point {
     }
 }
 
-struct Floor {
-    points: Vec<Point>
+pub struct Floor {
+    pub points: Vec<Point>
 }
 
 impl Floor {
-    fn from (input: String) -> Self {
-        let points = input.lines().map(Point::from).collect::<Vec<Point>>();
-        Floor { points }
+    pub fn from (input: &str) -> AocResult<Self> {
+        let points = input.lines().enumerate().map(| (line_idx, line) | {
+            Point::from(line).map_err(| err | err.with_line(line_idx + 1))
+        }).collect::<AocResult<Vec<Point>>>()?;
+        Ok(Floor { points })
     }
 }
 
 
+#[allow(clippy::ptr_arg)]
 fn get_pairs (points: &Vec<Point>) -> Vec<(&Point, &Point)> {
     let mut pairs: Vec<(&Point, &Point)> = Vec::new();
     for outer in 0..points.len() {
@@ -48,8 +68,8 @@ fn get_pairs (points: &Vec<Point>) -> Vec<(&Point, &Point)> {
 
 
 
-pub fn star_one (input: String) -> String {
-    let floor = Floor::from(input);
+pub fn star_one (input: &str) -> AocResult<Answer> {
+    let floor = phase::parse(|| Floor::from(input))?;
     let mut max_area = 0;
     for (Point(p1_x, p1_y), Point(p2_x, p2_y)) in get_pairs(&floor.points) {
         let rect_x = (p1_x.max(p2_x) - p1_x.min(p2_x)) + 1;
@@ -59,7 +79,7 @@ pub fn star_one (input: String) -> String {
             max_area = area;
         }
     }
-    max_area.to_string()
+    Ok(max_area.into())
 }
 
 struct Bounds {
@@ -70,6 +90,7 @@ struct Bounds {
 }
 
 impl Bounds {
+    #[allow(clippy::len_zero, clippy::needless_return, clippy::redundant_field_names)]
     fn from (point_set: &HashSet<Point>) -> Self {
         assert!(point_set.len() > 0, "Bounds for a point set must recieve a non-empty point set");
 
@@ -103,6 +124,8 @@ struct ChristmasFloor {
     points_set: HashSet<Point>,
     green_points_set: HashSet<Point>,
     red_tile_kinds: HashMap<Point, RedPointKind>,
+    // Kept for shape_fill, which is commented out in new()
+    #[allow(dead_code)]
     void: HashSet<Point>,
     solution: Option<(Point, Point)>,
     // x_ascending: Vec<Point>,
@@ -114,14 +137,16 @@ struct ChristmasFloor {
 
 impl ChristmasFloor {
 
-    fn connect_points (prev_pt: Point, pt: &Point, green_pts: &mut HashSet<Point>, green_line_pts: &mut HashMap<Point, GreenPoint>) {
+    fn connect_points (prev_pt: Point, pt: &Point, green_pts: &mut HashSet<Point>, green_line_pts: &mut HashMap<Point, GreenPoint>) -> AocResult<()> {
         let Point(prex, prey) = prev_pt;
         let Point(curx, cury) = *pt;
 
         let matchx = prex == curx;
         let matchy = prey == cury;
 
-        assert!((matchx || matchy) && !(matchx && matchy), "Either the x or the y of the previous point MUST match with the current point");
+        if !((matchx || matchy) && !(matchx && matchy)) {
+            return Err(AocError::new(format!("Either the x or the y of the previous point ({:?}) MUST match with the current point ({:?})", prev_pt, pt)));
+        }
         if matchx {
             let start_y = prey.min(cury) + 1;
             let end_y = prey.max(cury);
@@ -151,9 +176,10 @@ impl ChristmasFloor {
                 });
             }
         }
+        Ok(())
     }
 
-    fn get_red_pt_kind (prev_pt: Point, pt: Point, next_pt: Point) -> RedPointKind {
+    fn get_red_pt_kind (prev_pt: Point, pt: Point, next_pt: Point) -> AocResult<RedPointKind> {
         let Point(prev_x, prev_y) = prev_pt;
         let Point(curr_x, curr_y) = pt;
         let Point(next_x, next_y) = next_pt;
@@ -164,9 +190,13 @@ impl ChristmasFloor {
         let next_match_x = next_x == curr_x;
         let next_match_y = next_y == curr_y;
 
-        assert!((prev_match_x || prev_match_y) && !(prev_match_x && prev_match_y), "Either the x or the y of the previous point MUST match with the current point");
-        if prev_match_x {
-            assert!(!next_match_x, "Cannot be going in a straight line for two points in a row");
+        if !((prev_match_x || prev_match_y) && !(prev_match_x && prev_match_y)) {
+            return Err(AocError::new(format!("Either the x or the y of the previous point ({:?}) MUST match with the current point ({:?})", prev_pt, pt)));
+        }
+        let kind = if prev_match_x {
+            if next_match_x {
+                return Err(AocError::new("Cannot be going in a straight line for two points in a row"));
+            }
 
             if prev_y > curr_y {
                 if next_x > curr_x {
@@ -186,7 +216,9 @@ impl ChristmasFloor {
             }
         }
         else {
-            assert!(!next_match_y, "Cannot be going in a straight line for two points in a row");
+            if next_match_y {
+                return Err(AocError::new("Cannot be going in a straight line for two points in a row"));
+            }
             
             if prev_x > curr_x {
                 if next_y > curr_y {
@@ -204,10 +236,12 @@ impl ChristmasFloor {
                     RedPointKind::BottomRight
                 }
             }
-        }
+        };
+        Ok(kind)
     }
 
 
+    #[allow(dead_code)]
     fn shape_fill (bounds: &Bounds, red_pts: &HashSet<Point>, green_points: &HashSet<Point>, void_points: &mut HashSet<Point>) {
         let Bounds { min_x, max_x, min_y, max_y  } = &bounds;
 
@@ -241,23 +275,29 @@ impl ChristmasFloor {
         }
     }
 
-    fn from (floor: Floor) -> Self {
+    // Errors point at the line of the red tile that doesn't line up, since the floor's points are in input order
+    #[allow(clippy::redundant_field_names)]
+    fn from (floor: Floor) -> AocResult<Self> {
         let Floor { points} = floor;
 
         let mut points_iter = points.iter();
-        let first_pt = *points_iter.next().unwrap();
+        let Some(first_pt) = points_iter.next().copied() else {
+            return Err(AocError::new("There are no red tiles on the floor!"));
+        };
         let mut prev_pt = first_pt;
         
         let mut green_pts = HashSet::<Point>::new();
         let mut green_line_pts = HashMap::<Point, GreenPoint>::new();
 
         let mut points_set: HashSet<Point> = HashSet::from([ first_pt ]);
-        for pt in points_iter {
+        for (pt_idx, pt) in points_iter.enumerate() {
             points_set.insert(*pt);
-            ChristmasFloor::connect_points(prev_pt, pt, &mut green_pts, &mut green_line_pts);
+            ChristmasFloor::connect_points(prev_pt, pt, &mut green_pts, &mut green_line_pts)
+                .map_err(| err | err.with_line(pt_idx + 2))?;
             prev_pt = *pt;
         }
-        ChristmasFloor::connect_points(prev_pt, &first_pt, &mut green_pts, &mut green_line_pts);
+        ChristmasFloor::connect_points(prev_pt, &first_pt, &mut green_pts, &mut green_line_pts)
+            .map_err(| err | err.with_line(1))?;
 
         let mut red_pt_kinds = HashMap::new();
         for pt_idx in 0..points.len() {
@@ -276,17 +316,18 @@ impl ChristmasFloor {
             };
 
             let pt = points[pt_idx];
-            let pt_kind = ChristmasFloor::get_red_pt_kind(prev, pt, next);
+            let pt_kind = ChristmasFloor::get_red_pt_kind(prev, pt, next)
+                .map_err(| err | err.with_line(pt_idx + 1))?;
             red_pt_kinds.insert(pt, pt_kind);
         }
 
-        let mut void = HashSet::new();
+        let void = HashSet::new();
 
-        let bounds = Bounds::from(&points_set);
+        // let bounds = Bounds::from(&points_set);
         // let middle = Point((bounds.max_x - bounds.min_x) / 2 + 1, (bounds.max_y - bounds.min_y) / 2 + 1);
         // ChristmasFloor::shape_fill(&bounds, &points_set, &green_pts, &mut void);
         
-        ChristmasFloor { 
+        Ok(ChristmasFloor { 
             points, 
             points_set,
             green_points_set: green_pts,
@@ -297,13 +338,14 @@ impl ChristmasFloor {
             // y_ascending: y_ascending_vec,
             // x_ascending_map, 
             // y_ascending_map,
-        }
+        })
     }
 
 
 }
 
 impl std::fmt::Display for ChristmasFloor {
+    #[allow(clippy::writeln_empty_string)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 
         let Bounds { min_x, max_x, min_y, max_y  } = Bounds::from(&self.points_set);
@@ -367,9 +409,10 @@ impl std::fmt::Display for ChristmasFloor {
     }
 }
 
-pub fn star_two (input: String) -> String {
-    let floor = Floor::from(input);
-    let mut christmas_floor = ChristmasFloor::from(floor);
+#[allow(clippy::explicit_auto_deref)]
+pub fn star_two (input: &str) -> AocResult<Answer> {
+    let floor = phase::parse(|| Floor::from(input))?;
+    let mut christmas_floor = ChristmasFloor::from(floor)?;
     // println!("{christmas_floor}");
 
     let mut max_areas: Vec<usize> = vec![];
@@ -377,8 +420,7 @@ pub fn star_two (input: String) -> String {
     let mut max_area = 0;
     let mut max_pts = None;
     let pairs = get_pairs(&christmas_floor.points);
-    let pairs_len = pairs.len();
-    for (idx, (Point(p1_x, p1_y), Point(p2_x, p2_y))) in pairs.iter().enumerate() {
+    for (Point(p1_x, p1_y), Point(p2_x, p2_y)) in pairs.iter() {
         let max_x = *p1_x.max(p2_x);
         let max_y = *p1_y.max(p2_y);
 
@@ -428,9 +470,6 @@ pub fn star_two (input: String) -> String {
         // }
 
 
-        let mut collide_count  = 0;
-
-        let mut wrong_count = 0;
         let mut ok = true;
 
         let points = &christmas_floor.points;
@@ -482,7 +521,7 @@ pub fn star_two (input: String) -> String {
 
             if !red_pt_shares_a_line && prev_pt_shares_a_line && next_pt_shares_a_line {
                 if (p1_x, p1_y) == (&9,&5) && (p2_x, p2_y) == (&2,&3) || (p1_x, p1_y) == (&2,&3) && (p2_x, p2_y) == (&9,&5) {
-                    println!("BROKEN BY: {:?}", red_pt);
+                    trace!("BROKEN BY: {:?}", red_pt);
                 }
                 ok = false;
                 break;
@@ -582,7 +621,7 @@ pub fn star_two (input: String) -> String {
             // }
         }
 
-        if ok && collide_count % 2 == 0 {
+        if ok {
             let rect_x = (p1_x.max(p2_x) - p1_x.min(p2_x)) + 1;
             let rect_y = (p1_y.max(p2_y) - p1_y.min(p2_y)) + 1;
             
@@ -607,15 +646,14 @@ pub fn star_two (input: String) -> String {
     }
 
     christmas_floor.solution = max_pts;
-    println!("{christmas_floor}");
+    debug!("Floor with the largest rectangle:\n{christmas_floor}");
 
     possibles.sort();
-    println!("{:?}", possibles);
-    println!("{:?}", possibles.len());
-    println!("{:?}", max_areas);
-    println!("{:?}", max_pts);
+    trace!("{} possible rectangles: {:?}", possibles.len(), possibles);
+    debug!("Largest areas as they were found: {:?}", max_areas);
+    debug!("Largest rectangle: {:?}", max_pts);
     
-    max_area.to_string()
+    Ok(max_area.into())
 
     
 
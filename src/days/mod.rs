@@ -1,26 +1,69 @@
-pub(crate) mod day_1;
-pub(crate) mod day_2;
-pub(crate) mod day_2_regex_impl;
-pub(crate) mod day_3;
-pub(crate) mod day_4;
-pub(crate) mod day_5;
-pub(crate) mod day_6;
-pub(crate) mod day_7;
-pub(crate) mod day_8;
-pub(crate) mod day_9;
-pub(crate) mod day_10;
-pub(crate) mod day_11;
-pub(crate) mod day_12;
-pub(crate) mod day_13;
-pub(crate) mod day_14;
-pub(crate) mod day_15;
-pub(crate) mod day_16;
-pub(crate) mod day_17;
-pub(crate) mod day_18;
-pub(crate) mod day_19;
-pub(crate) mod day_20;
-pub(crate) mod day_21;
-pub(crate) mod day_22;
-pub(crate) mod day_23;
-pub(crate) mod day_24;
-pub(crate) mod day_25;
\ No newline at end of file
+pub mod day_1;
+pub mod day_2;
+pub mod day_2_regex_impl;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+
+use crate::report::Appendix;
+use crate::solution::Solution;
+
+// Every registered implementation, in calendar order
+// New days (or new implementations of old days) only need to be added here for the runner to pick them up
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day_1::Day1,
+    &day_2::Day2,
+    &day_2_regex_impl::Day2Regex,
+    &day_3::Day3,
+    &day_4::Day4,
+    &day_5::Day5,
+    &day_6::Day6,
+    &day_7::Day7,
+    &day_7::Day7Naive,
+    &day_8::Day8,
+    &day_9::Day9,
+    &day_10::Day10,
+    &day_10::Day10Iterative,
+    &day_11::Day11,
+    &day_12::Day12,
+    &day_13::Day13,
+    &day_14::Day14,
+    &day_15::Day15,
+    &day_16::Day16,
+    &day_17::Day17,
+    &day_18::Day18,
+    &day_19::Day19,
+    &day_20::Day20,
+    &day_21::Day21,
+    &day_22::Day22,
+    &day_23::Day23,
+    &day_24::Day24,
+    &day_25::Day25,
+];
+
+// Renderings of a day's input (through the `Display`s the days already have) that --report can add to its page
+pub static APPENDICES: &[Appendix] = &[
+    Appendix { day: 6, title: "Worksheet, read by humans", render: day_6::render_human_worksheet },
+    Appendix { day: 6, title: "Worksheet, read by cephalopods", render: day_6::render_cephalopod_worksheet },
+    Appendix { day: 7, title: "Tachyon manifold", render: day_7::render_manifold },
+];
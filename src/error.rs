@@ -0,0 +1,152 @@
+// Shared error type for every day's parsing and solving
+// Parsers fill in the (1-based) line and column of whatever they choked on where they can, and the `solution!`
+//      macro stamps the day on the way out, so the runner can point at the offending spot in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    pub day: Option<u8>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+pub type AocResult<T> = Result<T, AocError>;
+
+impl AocError {
+    pub fn new (message: impl Into<String>) -> Self {
+        AocError { day: None, line: None, column: None, message: message.into() }
+    }
+
+    pub fn on_line (line: usize, message: impl Into<String>) -> Self {
+        AocError { line: Some(line), ..AocError::new(message) }
+    }
+
+    pub fn at (line: usize, column: usize, message: impl Into<String>) -> Self {
+        AocError { line: Some(line), column: Some(column), ..AocError::new(message) }
+    }
+
+    // Finds the line and column of a byte offset into `input`, for parsers that work on the whole input at once
+    pub fn at_offset (input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &input[..offset.min(input.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(| idx | idx + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+        AocError::at(line, column, message)
+    }
+
+    pub fn with_day (self, day: u8) -> Self {
+        AocError { day: Some(day), ..self }
+    }
+
+    // For errors from helpers that only see a piece of a line (like a single character)
+    pub fn with_position (self, line: usize, column: usize) -> Self {
+        AocError { line: Some(line), column: Some(column), ..self }
+    }
+
+    // For errors from helpers that only see a single line, and so only know the column
+    pub fn with_line (self, line: usize) -> Self {
+        AocError { line: Some(line), ..self }
+    }
+
+    pub fn with_column (self, column: usize) -> Self {
+        AocError { column: Some(column), ..self }
+    }
+
+    // The error message followed by the offending line of `input`, with a caret under the column if there is one
+    //
+    //      error: day 4, line 2, column 3: The only characters allowed in the grid are ...
+    //        |
+    //      2 | ..x.@
+    //        |   ^
+    pub fn render (&self, input: &str) -> String {
+        let mut rendered = format!("error: {}", self);
+
+        let Some(line) = self.line else {
+            return rendered;
+        };
+        let Some(text) = input.lines().nth(line.saturating_sub(1)) else {
+            return rendered;
+        };
+
+        let gutter = line.to_string().len();
+        rendered += &format!("\n{:>gutter$} |", "", gutter = gutter);
+        rendered += &format!("\n{} | {}", line, text);
+        if let Some(column) = self.column {
+            rendered += &format!("\n{:>gutter$} | {:>column$}", "", "^", gutter = gutter, column = column);
+        }
+        rendered
+    }
+}
+
+impl std::fmt::Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut location: Vec<String> = Vec::new();
+        if let Some(day) = self.day {
+            location.push(format!("day {}", day));
+        }
+        if let Some(line) = self.line {
+            location.push(format!("line {}", line));
+        }
+        if let Some(column) = self.column {
+            location.push(format!("column {}", column));
+        }
+
+        if location.is_empty() {
+            write!(f, "{}", self.message)
+        }
+        else {
+            write!(f, "{}: {}", location.join(", "), self.message)
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_offset_finds_line_and_column () {
+        let input = "11-22,33-44\n55-x6";
+        let err = AocError::at_offset(input, 15, "bad");
+        assert_eq!((err.line, err.column), (Some(2), Some(4)));
+
+        let err = AocError::at_offset(input, 0, "bad");
+        assert_eq!((err.line, err.column), (Some(1), Some(1)));
+
+        // Right after a line break is the start of the next line
+        let err = AocError::at_offset(input, 12, "bad");
+        assert_eq!((err.line, err.column), (Some(2), Some(1)));
+    }
+
+    #[test]
+    fn at_offset_counts_columns_in_characters () {
+        let err = AocError::at_offset("é-x", 3, "bad");
+        assert_eq!((err.line, err.column), (Some(1), Some(3)));
+    }
+
+    #[test]
+    fn render_puts_the_caret_under_the_column () {
+        let err = AocError::at(2, 3, "bad character").with_day(4);
+        assert_eq!(err.render("....\n..x.@"), [
+            "error: day 4, line 2, column 3: bad character",
+            "  |",
+            "2 | ..x.@",
+            "  |   ^",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn render_widens_the_gutter_for_long_line_numbers () {
+        let input = "x\n".repeat(9) + "abc";
+        let err = AocError::at(10, 1, "bad");
+        assert_eq!(err.render(&input), "error: line 10, column 1: bad\n   |\n10 | abc\n   | ^");
+    }
+
+    #[test]
+    fn render_without_a_position_is_just_the_message () {
+        assert_eq!(AocError::new("bad").render("abc"), "error: bad");
+        assert_eq!(AocError::on_line(1, "bad").render("abc"), "error: line 1: bad\n  |\n1 | abc");
+        assert_eq!(AocError::on_line(5, "bad").render("abc"), "error: line 5: bad");
+    }
+}
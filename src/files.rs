@@ -0,0 +1,47 @@
+use std::{fs, io::{self, Write}, path::Path};
+use serde::{Serialize, de::DeserializeOwned};
+
+// The runner's own files: aoc.toml, the answers.toml files, and the JSON Lines history and cache
+//
+// None of them have to exist, a missing file is the same as an empty one, so a fresh checkout (or an explicit
+//      --config pointing at a file that isn't there yet) just runs with nothing in them
+
+// The file's contents, None when there is no such file
+pub fn read_optional (path: &Path) -> Result<Option<String>, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(format!("Could not read '{}': {}", path.display(), err)),
+    }
+}
+
+// One entry per line of a JSON Lines file, blank lines skipped
+// A line that doesn't parse only fails itself, so callers decide whether that's fatal (collect into a Result)
+//      or just means skipping it
+pub fn read_json_lines <T: DeserializeOwned> (path: &Path) -> Result<Vec<Result<T, String>>, String> {
+    let contents = read_optional(path)?.unwrap_or_default();
+    let entries = contents.lines()
+        .enumerate()
+        .filter(| (_, line) | !line.trim().is_empty())
+        .map(| (line_idx, line) | {
+            serde_json::from_str(line).map_err(| err | format!("Could not parse '{}' line {}: {}", path.display(), line_idx + 1, err))
+        })
+        .collect();
+    Ok(entries)
+}
+
+pub fn append_json_lines <T: Serialize> (path: &Path, entries: &[T]) -> io::Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut lines = String::new();
+    for entry in entries {
+        lines += &serde_json::to_string(entry).map_err(io::Error::other)?;
+        lines += "\n";
+    }
+
+    // One write, so concurrent runs appending to the same file don't interleave their lines
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(lines.as_bytes())
+}
@@ -0,0 +1,216 @@
+use std::{collections::BTreeMap, io, path::Path, process::Command, sync::OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use owo_colors::Style;
+use serde::{Deserialize, Serialize};
+use crate::files;
+use crate::output;
+use crate::runner::format_duration;
+use crate::solution::Part;
+
+// Every timed run gets appended to a JSON Lines history file (.aoc-history.jsonl by default), one run per line:
+//
+//      {"timestamp":1765432100,"day":8,"part":1,"implementation":"default","input_hash":"9f2c...","git_rev":"a745e1a","elapsed_nanos":1234,"jobs":1,"mode":"run"}
+//
+// Runs are only comparable with the same day, part, implementation, input, and mode, see `HistoryEntry::key`
+pub const DEFAULT_HISTORY_FILE: &str = ".aoc-history.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    // Seconds since the unix epoch
+    pub timestamp: u64,
+    pub day: u8,
+    pub part: u8,
+    pub implementation: String,
+    pub input_hash: String,
+    pub git_rev: Option<String>,
+    pub elapsed_nanos: u128,
+
+    // How many stars were running at once, anything over 1 is a contended wall-clock time
+    pub jobs: usize,
+
+    // "run" for single runs and --all, "bench" for the median of a --bench
+    pub mode: String,
+}
+
+impl HistoryEntry {
+    pub fn new (day: u8, part: Part, implementation: &str, input: &str, elapsed: Duration, jobs: usize, mode: &str) -> Self {
+        HistoryEntry {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(| since | since.as_secs()).unwrap_or(0),
+            day,
+            part: match part { Part::One => 1, Part::Two => 2 },
+            implementation: implementation.to_string(),
+            input_hash: input_hash(input),
+            git_rev: git_rev(),
+            elapsed_nanos: elapsed.as_nanos(),
+            jobs,
+            mode: mode.to_string(),
+        }
+    }
+
+    pub fn elapsed (&self) -> Duration {
+        Duration::from_nanos(self.elapsed_nanos.min(u64::MAX as u128) as u64)
+    }
+
+    // Runs with the same key are timings of the same work, measured the same way
+    pub fn key (&self) -> (u8, u8, &str, &str, &str) {
+        (self.day, self.part, &self.implementation, &self.input_hash, &self.mode)
+    }
+}
+
+pub fn input_hash (input: &str) -> String {
+    content_hash(input.as_bytes())
+}
+
+// 64 bit FNV-1a, written out by hand because std's hashers aren't guaranteed to be stable between releases
+// and the hashes have to match across runs of different builds
+pub fn content_hash (bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf29ce484222325u64, | hash, byte | {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+// Short revision of the checkout this binary was built from, with "-dirty" when there are uncommitted changes
+// None when git (or the checkout) isn't around, looked up once per process
+pub fn git_rev () -> Option<String> {
+    static GIT_REV: OnceLock<Option<String>> = OnceLock::new();
+    GIT_REV.get_or_init(|| {
+        let git = | args: &[&str] | -> Option<String> {
+            let output = Command::new("git").args(args).current_dir(env!("CARGO_MANIFEST_DIR")).output().ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        let rev = git(&[ "rev-parse", "--short", "HEAD" ])?;
+        let dirty = git(&[ "status", "--porcelain", "--untracked-files=no" ]).is_some_and(| status | !status.is_empty());
+        Some(if dirty { format!("{}-dirty", rev) } else { rev })
+    }).clone()
+}
+
+pub fn append (path: &Path, entries: &[HistoryEntry]) -> io::Result<()> {
+    files::append_json_lines(path, entries)
+}
+
+pub fn load (path: &Path) -> Result<Vec<HistoryEntry>, String> {
+    files::read_json_lines(path)?.into_iter().collect()
+}
+
+// How the runs of one key (see `HistoryEntry::key`) have gone over time
+pub struct Trend {
+    pub day: u8,
+    pub part: u8,
+    pub implementation: String,
+    pub input_hash: String,
+    pub mode: String,
+    pub runs: usize,
+    pub best: Duration,
+    pub latest: Duration,
+
+    // How much slower the latest run is than the best, in percent
+    pub slower: f64,
+
+    // The last few runs, oldest first
+    pub recent: Vec<Duration>,
+    pub git_rev: Option<String>,
+}
+
+impl Trend {
+    fn short_hash (&self) -> &str {
+        &self.input_hash[..8.min(self.input_hash.len())]
+    }
+}
+
+pub struct Trends {
+    pub trends: Vec<Trend>,
+
+    // Percent slower than the best before the latest run counts as a regression
+    pub slower_than: f64,
+}
+
+impl Trends {
+    // Contended runs (more than one job at once) aren't comparable, so they're left out
+    pub fn from (entries: &[HistoryEntry], day: Option<u8>, slower_than: f64) -> Self {
+        let mut groups: BTreeMap<(u8, u8, &str, &str, &str), Vec<&HistoryEntry>> = BTreeMap::new();
+        for entry in entries {
+            if entry.jobs > 1 || day.is_some_and(| day | day != entry.day) {
+                continue;
+            }
+            groups.entry(entry.key()).or_default().push(entry);
+        }
+
+        let trends = groups.into_iter().map(| ((day, part, implementation, input_hash, mode), runs) | {
+            // The file is append only, so the last run is the latest
+            let latest = runs[runs.len() - 1];
+            let best = runs.iter().map(| run | run.elapsed()).min().unwrap_or_default();
+            Trend {
+                day,
+                part,
+                implementation: implementation.to_string(),
+                input_hash: input_hash.to_string(),
+                mode: mode.to_string(),
+                runs: runs.len(),
+                best,
+                latest: latest.elapsed(),
+                slower: (latest.elapsed().as_secs_f64() / best.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0) * 100.0,
+                recent: runs.iter().rev().take(10).rev().map(| run | run.elapsed()).collect(),
+                git_rev: latest.git_rev.clone(),
+            }
+        }).collect();
+
+        Trends { trends, slower_than }
+    }
+
+    // Stars whose latest run is more than `slower_than` percent slower than their best
+    pub fn regressions (&self) -> Vec<&Trend> {
+        self.trends.iter().filter(| trend | trend.slower > self.slower_than).collect()
+    }
+
+    pub fn text (&self) -> String {
+        let rows = self.trends.iter().map(| trend | vec![
+            trend.day.to_string(),
+            trend.part.to_string(),
+            trend.implementation.clone(),
+            trend.short_hash().to_string(),
+            trend.mode.clone(),
+            trend.runs.to_string(),
+            format_duration(trend.best),
+            format_duration(trend.latest),
+            format!("{:+.1}%", trend.slower),
+            sparkline(&trend.recent),
+            trend.git_rev.clone().unwrap_or(String::from("-")),
+        ]).collect::<Vec<Vec<String>>>();
+
+        let mut text = output::table(&[ "Day", "Part", "Impl", "Input", "Mode", "Runs", "Best", "Latest", "vs best", "Trend", "Rev" ], &rows);
+        let regressions = self.regressions();
+        if !regressions.is_empty() {
+            text += "\n";
+        }
+        for trend in regressions {
+            text += &format!(
+                "\n{} Day {:>2} star {} ({}, input {}, {}): latest run is {:.1}% slower than the best ({} vs {})",
+                output::paint("SLOWER", Style::new().yellow().bold()), trend.day, trend.part, trend.implementation, trend.short_hash(), trend.mode,
+                trend.slower, format_duration(trend.latest), format_duration(trend.best)
+            );
+        }
+        text
+    }
+}
+
+// Bars for the last few runs of a star, scaled between the fastest and slowest of them
+pub fn sparkline (durations: &[Duration]) -> String {
+    let bars = [ '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█' ];
+    let (Some(min), Some(max)) = (durations.iter().min(), durations.iter().max()) else {
+        return String::new();
+    };
+    let range = (*max - *min).as_secs_f64();
+
+    durations.iter().map(| duration | {
+        if range == 0.0 {
+            return bars[0];
+        }
+        let scaled = (*duration - *min).as_secs_f64() / range;
+        bars[(scaled * (bars.len() - 1) as f64).round() as usize]
+    }).collect()
+}
@@ -0,0 +1,125 @@
+// Puzzle inputs come from wherever they were saved: a browser on Windows leaves CRLF line endings, some
+//      editors add a byte order mark, and whether the file ends with a newline depends on who copied it
+// The runner puts every input through `normalize` so the days only ever have to deal with one shape of input:
+//      no BOM, '\n' line endings, and no line breaks after the last line
+// Whitespace inside a line is left alone, column aligned puzzles (like day 6's worksheet) depend on it
+//
+// Days that want the input exactly as it was read opt out through `Solution::raw_input`
+//
+// Inputs are normalized once, when they're loaded into an `Input`, not every time a star runs
+use std::{borrow::Cow, ops::Range, sync::Arc};
+use crate::solution::Solution;
+
+const BOM: char = '\u{feff}';
+
+// A puzzle input as it was read, and normalized
+// Inputs are loaded once and then shared by every run of them (both stars, every implementation, every bench
+//      iteration), so both are kept in an `Arc` that's only ever borrowed from and clones are cheap
+#[derive(Debug, Clone)]
+pub struct Input {
+    raw: Arc<str>,
+    normalized: Normalized,
+}
+
+// Normalizing mostly just trims the input, so it's a slice of the raw one unless line endings had to change
+#[derive(Debug, Clone)]
+enum Normalized {
+    Slice(Range<usize>),
+    Owned(Arc<str>),
+}
+
+impl Input {
+    pub fn raw (&self) -> &str {
+        &self.raw
+    }
+
+    pub fn normalized (&self) -> &str {
+        match &self.normalized {
+            Normalized::Slice(range) => &self.raw[range.clone()],
+            Normalized::Owned(normalized) => normalized,
+        }
+    }
+
+    // The input `solution` gets to see
+    pub fn for_solution (&self, solution: &dyn Solution) -> &str {
+        if solution.raw_input() {
+            self.raw()
+        }
+        else {
+            self.normalized()
+        }
+    }
+}
+
+impl From<String> for Input {
+    fn from(raw: String) -> Self {
+        let raw: Arc<str> = Arc::from(raw);
+        let normalized = match normalize(&raw) {
+            Cow::Borrowed(normalized) => {
+                let start = normalized.as_ptr() as usize - raw.as_ptr() as usize;
+                Normalized::Slice(start..start + normalized.len())
+            },
+            Cow::Owned(normalized) => Normalized::Owned(Arc::from(normalized)),
+        };
+        Input { raw, normalized }
+    }
+}
+
+// Borrows the input whenever it can, only CRLF line endings need a new copy of it
+pub fn normalize (input: &str) -> Cow<'_, str> {
+    if !needs_normalizing(input) {
+        return Cow::Borrowed(input);
+    }
+
+    let without_bom = input.strip_prefix(BOM).unwrap_or(input);
+    if !without_bom.contains("\r\n") {
+        return Cow::Borrowed(without_bom.trim_end_matches('\n'));
+    }
+
+    let mut normalized = without_bom.replace("\r\n", "\n");
+    let trimmed_len = normalized.trim_end_matches('\n').len();
+    normalized.truncate(trimmed_len);
+    Cow::Owned(normalized)
+}
+
+fn needs_normalizing (input: &str) -> bool {
+    input.starts_with(BOM)
+        || input.ends_with('\n')
+        || input.contains("\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_input_is_borrowed () {
+        assert!(matches!(normalize("a\nb"), Cow::Borrowed("a\nb")));
+    }
+
+    #[test]
+    fn bom_and_trailing_newlines_are_trimmed_without_copying () {
+        assert!(matches!(normalize("\u{feff}a\nb\n\n"), Cow::Borrowed("a\nb")));
+    }
+
+    #[test]
+    fn bom_crlf_and_trailing_newlines () {
+        assert_eq!(normalize("\u{feff}a\r\nb\r\n\r\n"), "a\nb");
+        assert_eq!(normalize("a\r\n  b  \r\n"), "a\n  b  ");
+    }
+
+    #[test]
+    fn lone_carriage_returns_are_kept () {
+        assert_eq!(normalize("a\rb\n"), "a\rb");
+    }
+
+    #[test]
+    fn loaded_input_keeps_the_raw_text () {
+        let input = Input::from(String::from("\u{feff}a\r\nb\r\n"));
+        assert_eq!(input.raw(), "\u{feff}a\r\nb\r\n");
+        assert_eq!(input.normalized(), "a\nb");
+
+        let input = Input::from(String::from("\u{feff}a\nb\n"));
+        assert_eq!(input.normalized(), "a\nb");
+    }
+}
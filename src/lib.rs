@@ -0,0 +1,23 @@
+pub mod days;
+pub mod error;
+pub mod solution;
+pub mod runner;
+pub mod bench;
+pub mod answers;
+pub mod answer;
+pub mod output;
+pub mod alloc;
+pub mod watch;
+pub mod pool;
+pub mod history;
+pub mod input;
+pub mod params;
+pub mod config;
+pub mod cache;
+pub mod report;
+pub mod log;
+pub mod phase;
+pub mod files;
+pub mod batch;
+pub mod check;
+pub mod cross_check;
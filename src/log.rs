@@ -0,0 +1,84 @@
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use crate::solution::Part;
+
+// Leveled logging for solutions (and the runner), so debugging output doesn't end up mixed in with the answers
+// Everything goes to stderr, and only what's at or above the level picked with -v, -vv, or --quiet is printed:
+//
+//      --quiet     errors only
+//      (default)   warnings, and info like --watch's status lines
+//      -v          debug events, like day 9's rendered floor
+//      -vv         trace events, like day 7's universe count on every step
+//
+// Days log through the `debug!` and `trace!` macros, which skip formatting their arguments entirely when their
+//      level is off, so leaving them in a hot loop only costs a load and a compare
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Error => write!(f, "error"),
+            Level::Warn => write!(f, "warn"),
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+pub fn set_max_level (level: Level) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn enabled (level: Level) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+}
+
+thread_local! {
+    static STAR: Cell<Option<(u8, Part)>> = const { Cell::new(None) };
+}
+
+// Tags everything logged on this thread with the star that's running (see `runner::run_caught`), so the output
+//      of batch runs spread over several threads can still be told apart
+pub fn set_star (star: Option<(u8, Part)>) {
+    STAR.with(| current | current.set(star));
+}
+
+pub fn emit (level: Level, message: fmt::Arguments) {
+    if !enabled(level) {
+        return;
+    }
+
+    match STAR.with(Cell::get) {
+        Some((day, part)) => eprintln!("[{}] day {} star {}: {}", level, day, part, message),
+        None => eprintln!("[{}] {}", level, message),
+    }
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            $crate::log::emit($crate::log::Level::Debug, format_args!($($arg)*));
+        }
+    };
+}
+pub(crate) use debug;
+
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            $crate::log::emit($crate::log::Level::Trace, format_args!($($arg)*));
+        }
+    };
+}
+pub(crate) use trace;
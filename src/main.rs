@@ -1,15 +1,27 @@
-
-
-mod days;
-
-use std::{fs, path::Path};
-use std::time::{Duration, Instant};
-use structopt::StructOpt;
+use std::{path::{Path, PathBuf}, process};
+use std::time::Duration;
+use structopt::{StructOpt, clap::ArgMatches};
+use aoc_2025::batch;
+use aoc_2025::bench::{self, BenchOpts};
+use aoc_2025::cache::{self, Cache, CacheEntry};
+use aoc_2025::check;
+use aoc_2025::config::{self, Config, Settings};
+use aoc_2025::cross_check;
+use aoc_2025::history::{self, HistoryEntry, Trends};
+use aoc_2025::log::{self, Level};
+use aoc_2025::output::{self, ColorChoice, Format, Record};
+use aoc_2025::params::{self, Params};
+use aoc_2025::phase::{self, PhaseTime};
+use aoc_2025::pool;
+use aoc_2025::report::{self, ReportOpts};
+use aoc_2025::runner::{self, DataSet, InputSource, Outcome, format_duration};
+use aoc_2025::solution::{self, Part, Solution};
+use aoc_2025::watch::{self, WatchOpts};
 
 #[derive(Debug, StructOpt)]
 struct Opt {
-    #[structopt(short, long, parse(try_from_str = parse_day), help="Date to run.")]
-    day: u8,
+    #[structopt(short, long, parse(try_from_str = parse_day), required_unless_one = &["list", "all", "history", "report"], help="Date to run.")]
+    day: Option<u8>,
 
     #[structopt(long, help="Flag to tell whether to use the example data set or the full data.")]
     example: bool,
@@ -20,8 +32,110 @@ struct Opt {
     #[structopt(short, long, default_value="./data", help="Root folder of data directory.  Should contain 25 files [1...25] each with an \"example.txt\" and \"full.txt\" file for running with AOC data.")]
     input_folder: String,
 
-    #[structopt(short = "o", long = "other", help="Flag to tell whether to run the \"other\" implementation.  Only applicable for days where you've done another implementation.")]
+    #[structopt(long, conflicts_with_all = &["all", "check", "example"], help="Read the puzzle input from this file instead of the data folder, or from stdin if \"-\".")]
+    input: Option<String>,
+
+    #[structopt(long = "impl", default_value = solution::DEFAULT_IMPL, help="Name of the implementation to run, see --list-impls.  Can't be used with --all.")]
+    implementation: String,
+
+    #[structopt(short = "o", long = "other", requires = "day", conflicts_with = "all", help="Deprecated, use --impl instead.  Runs the day's first implementation other than the default one.")]
     other_impl: bool,
+
+    #[structopt(long = "list-impls", requires = "day", help="List the implementations registered for --day, then exit.")]
+    list_impls: bool,
+
+    #[structopt(long = "param", value_name = "key=value", parse(try_from_str = params::parse_param), number_of_values = 1, conflicts_with_all = &["all", "check", "report"], help="Change one of the day's puzzle constants, like --param dial_size=120 for day 1.  Can be given more than once.  Runs with parameters aren't added to the history.")]
+    params: Vec<(String, String)>,
+
+    #[structopt(long, parse(try_from_str = parse_timeout), help="Give up on a star after this long (like 500ms, 30s, or 2m, plain numbers are seconds) and report it as timed out.  Batch runs move on to the next star, the timed out one keeps running in the background until the process exits.")]
+    timeout: Option<Duration>,
+
+    #[structopt(long, default_value = "text", help="Output format for single runs and --all: text, json, or csv.  JSON and CSV have one record per star with day, part, implementation, input, status, answer, elapsed_nanos, error, and (with the alloc-stats feature) allocations, bytes_allocated, and peak_bytes.")]
+    format: Format,
+
+    #[structopt(long, help="Show timing trends from the history file (for --day, or every day) and warn about any star that's gotten slower than its best run, then exit.  Exits non-zero if anything got slower.")]
+    history: bool,
+
+    #[structopt(long, default_value = history::DEFAULT_HISTORY_FILE, help="JSON Lines file that every timed run is appended to.")]
+    history_file: String,
+
+    #[structopt(long, help="Don't append this run's timings to the history file.")]
+    no_history: bool,
+
+    #[structopt(long, default_value = "10", help="With --history, warn when a star's latest run is more than this many percent slower than its best.")]
+    slower_than: f64,
+
+    #[structopt(long, help="Reuse the answer of an earlier single run by the same build, with the same star, implementation, parameters, and input, instead of solving it again.")]
+    cache: bool,
+
+    #[structopt(long, help="With --cache, solve the star again even if its answer is cached (the new answer replaces the cached one).")]
+    no_cache: bool,
+
+    #[structopt(long, default_value = cache::DEFAULT_CACHE_FILE, help="JSON Lines file that --cache keeps its answers in.")]
+    cache_file: String,
+
+    #[structopt(short, long, help="Threads to spread --all and --check runs over.  Defaults to one per CPU.")]
+    jobs: Option<usize>,
+
+    #[structopt(long, conflicts_with = "jobs", help="Run --all and --check one star at a time, for timings without contention.  --bench and --cross-check are always serial.")]
+    serial: bool,
+
+    #[structopt(long, conflicts_with_all = &["all", "bench", "check", "cross-check"], help="Keep running --day, re-running it whenever a .txt file in its data folder (or the --input file) changes.  Also rebuilds with cargo when the crate's sources change, and restarts when the binary is rebuilt.")]
+    watch: bool,
+
+    #[structopt(long = "cross-check", conflicts_with_all = &["bench", "check"], help="Run every implementation of --day (or of every day with --all) on the same input, report any answers they disagree on, and compare their timings.  Exits non-zero on any disagreement, or if any of them panics, fails, or times out.  Without --timeout, each run gives up after 10s.")]
+    cross_check: bool,
+
+    #[structopt(short, long, parse(from_occurrences), help="Print the solutions' debug events to stderr, -vv for their trace events too.")]
+    verbose: u8,
+
+    #[structopt(short, long, conflicts_with = "verbose", help="Only print errors to stderr, and only the answer of single runs to stdout.")]
+    quiet: bool,
+
+    #[structopt(long, default_value = "auto", help="Color PASS/FAIL markers and warnings: auto (only when printing to a terminal and NO_COLOR isn't set), always, or never.")]
+    color: ColorChoice,
+
+    #[structopt(long, help="Read runner defaults from this TOML file instead of ./aoc.toml (or aoc.toml in $XDG_CONFIG_HOME).  Flags given on the command line always win over the file.")]
+    config: Option<String>,
+
+    #[structopt(long, conflicts_with = "config", help="Ignore any aoc.toml.")]
+    no_config: bool,
+
+    #[structopt(long, conflicts_with_all = &["all", "input", "bench", "check", "cross-check", "watch"], help="Run one star of --day over every .txt file in this folder (<input-folder>/<day>/inputs when no folder is given) and print each file's answer and timing.  Exits non-zero if any of them didn't produce an answer.")]
+    inputs: Option<Option<String>>,
+
+    #[structopt(long, conflicts_with_all = &["all", "input", "bench", "check", "cross-check", "watch", "inputs"], help="Time every implementation of every implemented day (or just --day) the way --bench does, and write the answers and median times to report.md and report.html in this folder.  Without --timeout, every run of a star gives up after 10s.")]
+    report: Option<String>,
+
+    #[structopt(long, requires = "report", help="With --report, leave the answers out of the report.")]
+    redact: bool,
+
+    #[structopt(long, requires = "report", help="With --report, add renderings of the inputs of days that have one (like day 6's worksheet) at the end of the report.")]
+    appendices: bool,
+
+    #[structopt(long, help="List every registered day and implementation, then exit.")]
+    list: bool,
+
+    #[structopt(long, conflicts_with = "day", help="Run both stars of every day (with the default implementation) and print a summary table.")]
+    all: bool,
+
+    #[structopt(long, requires = "all", help="With --all, run both the example and the full data sets instead of just the one picked by --example.")]
+    both: bool,
+
+    #[structopt(long, help="Benchmark both stars of --day (or of every day with --all) instead of timing a single run.")]
+    bench: bool,
+
+    #[structopt(long, default_value="3", help="Untimed runs before measuring, for --bench.")]
+    warmup: usize,
+
+    #[structopt(long, default_value="10", parse(try_from_str = parse_iterations), help="Measured runs per day and star, for --bench.")]
+    iterations: usize,
+
+    #[structopt(long, requires = "bench", help="With --bench, benchmark every implementation of a day side by side.")]
+    compare: bool,
+
+    #[structopt(long, conflicts_with = "bench", help="Check both stars of --day (or of every day with --all) on both data sets against the answers in <input-folder>/<day>/answers.toml.  Exits non-zero on any mismatch.")]
+    check: bool,
 }
 
 fn parse_day(s: &str) -> Result<u8, &'static str> {
@@ -32,270 +146,473 @@ fn parse_day(s: &str) -> Result<u8, &'static str> {
     }
 }
 
-fn format_duration(duration: Duration) -> String {
-    let total_nanos = duration.as_nanos();
-
-    let hours = (total_nanos / (1_000_000_000 * 60 * 60)) % 24;
-    let minutes = (total_nanos / (1_000_000_000 * 60)) % 60;
-    let seconds = (total_nanos / 1_000_000_000) % 60;
+fn parse_iterations(s: &str) -> Result<usize, &'static str> {
+    let num: usize = s.parse().map_err(|_| "Not a valid number")?;
+    match num {
+        0 => Err("--iterations must be at least 1"),
+        _ => Ok(num),
+    }
+}
 
-    let nanoseconds = total_nanos % 1_000_000_000;
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    let (number, unit) = match s.find(| chr: char | !chr.is_ascii_digit() && chr != '.') {
+        Some(idx) => s.split_at(idx),
+        None => (s, "s"),
+    };
+    let number: f64 = number.parse().map_err(|_| format!("Not a valid duration: \"{}\"", s))?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 60.0 * 60.0,
+        _ => return Err(format!("Unknown duration unit \"{}\", expected ms, s, m, or h", unit)),
+    };
+    match seconds {
+        0.0 => Err(String::from("--timeout must be longer than zero")),
+        _ => Duration::try_from_secs_f64(seconds).map_err(| err | format!("Not a valid duration: \"{}\" ({})", s, err)),
+    }
+}
 
-    // Add commas to the nano seconds
-    // Maybe there's a better way to do this, but for now I'm just reversing the string, replacing every three pairs
-    //      of digits with those same digits plus a comma, then reversing again
-    let nano_str = nanoseconds.to_string().chars().rev().collect::<String>();
-    let reg = regex::Regex::new(r"(\d{3})").unwrap();
-    let nano_comma_str= reg.replace_all(&nano_str, "$1,").chars().rev().collect::<String>();
-    let nano_comma_str = if nano_comma_str.starts_with(",") {
-        &nano_comma_str[1..]
+fn list_solutions () {
+    for day in 1..=25 {
+        let names = solution::for_day(day).iter()
+            .map(| solution | solution.name())
+            .collect::<Vec<&str>>();
+        let implemented = solution::for_day(day).iter().any(| solution | solution.implemented());
+        println!("Day {:>2}: {}{}", day, names.join(", "), if implemented { "" } else { " (not implemented)" });
     }
-    else {
-        &nano_comma_str
+}
+
+// Parse and solve times under the elapsed time, with any phases nested in them indented below
+// Nothing is printed for days that don't time their parse, all of their elapsed time is solving
+fn print_phases (elapsed: Duration, phases: &[PhaseTime]) {
+    let (Some(parse), solve) = phase::split(elapsed, phases) else {
+        return;
+    };
+
+    let breakdown = | in_parse: bool | {
+        for phase in phases.iter().filter(| phase | (phase.path[0] == phase::PARSE) == in_parse && phase.path != [ phase::PARSE ]) {
+            let depth = if in_parse { phase.depth() } else { phase.depth() + 1 };
+            let runs = if phase.count > 1 { format!(" ({} runs)", phase.count) } else { String::new() };
+            println!("{}{}: {}{}", "    ".repeat(depth), phase.name(), format_duration(phase.elapsed), runs);
+        }
     };
 
-    format!("{:02}:{:02}:{:02} {} nanos", hours, minutes, seconds, nano_comma_str)
+    println!("Parse: {}", format_duration(parse));
+    breakdown(true);
+    println!("Solve: {}", format_duration(solve));
+    breakdown(false);
 }
 
-fn main() {
-    let opt = Opt::from_args();
-    let day = opt.day;
-    let is_example_file = opt.example;
-    let is_second_test = opt.second;
+// Failing to record history shouldn't fail the run, so this only complains
+fn record_history (history_file: Option<&Path>, entries: &[HistoryEntry]) {
+    if let Some(path) = history_file
+        && let Err(err) = history::append(path, entries) {
+        log::emit(Level::Warn, format_args!("Could not record timing history in '{}': {}", path.display(), err));
+    }
+}
+
+// Fills in everything that wasn't given on the command line from the config file's settings for the day
+// --impl and --param don't apply to --all (and --param not to --check), so the file's aren't used there either
+fn apply_config (opt: &mut Opt, matches: &ArgMatches, settings: Settings) -> Result<(), String> {
+    let from_cli = | name: &str | matches.occurrences_of(name) > 0;
 
-    let data_folder_root = opt.input_folder;
-    let mut path = Path::new(&data_folder_root).join(day.to_string());
-    if is_example_file {
-        path = path.join("example.txt")
+    if let (false, Some(input_folder)) = (from_cli("input-folder"), &settings.input_folder) {
+        opt.input_folder = input_folder.clone();
     }
-    else {
-        path = path.join("full.txt")
+    if let (false, Some(timeout)) = (from_cli("timeout"), &settings.timeout) {
+        opt.timeout = Some(parse_timeout(timeout).map_err(| err | format!("timeout: {}", err))?);
+    }
+    if let (false, Some(color)) = (from_cli("color"), &settings.color) {
+        opt.color = color.parse().map_err(| err | format!("color: {}", err))?;
+    }
+    if let (false, Some(format)) = (from_cli("format"), &settings.format) {
+        opt.format = format.parse().map_err(| err | format!("format: {}", err))?;
+    }
+    if let (false, Some(jobs)) = (from_cli("jobs") || from_cli("serial"), settings.jobs) {
+        opt.jobs = Some(jobs);
+    }
+    if let (false, Some(history_file)) = (from_cli("history-file"), &settings.history_file) {
+        opt.history_file = history_file.clone();
+    }
+    if let (false, Some(cache)) = (from_cli("cache"), settings.cache) {
+        opt.cache = cache;
+    }
+    if let (false, Some(cache_file)) = (from_cli("cache-file"), &settings.cache_file) {
+        opt.cache_file = cache_file.clone();
+    }
+    if let (false, false, Some(implementation)) = (from_cli("implementation") || opt.other_impl, opt.all, &settings.implementation) {
+        opt.implementation = implementation.clone();
     }
 
-    let file_read_result = fs::read_to_string(&path);
-    let file_contents = match file_read_result {
-        Ok(contents) => contents,
-        Err(err) => panic!("An error occurred while reading the data file at '{}': {}", path.as_os_str().to_str().unwrap(), err),
-    };
+    // Keys from the command line replace the same keys from the file, see `Params::new`
+    if !opt.all && !opt.check {
+        opt.params = [ settings.param_pairs(), opt.params.clone() ].concat();
+    }
+    Ok(())
+}
 
-    let start = Instant::now();
+// Exits with the list of available implementations when `name` isn't one of them
+fn select_impl (day: u8, name: &str) -> &'static dyn Solution {
+    match solution::find(day, name) {
+        Some(solution) => solution,
+        None => {
+            let names = solution::for_day(day).iter()
+                .map(| solution | solution.name())
+                .collect::<Vec<&str>>();
+            eprintln!("Day {} has no implementation named \"{}\" (available: {})", day, name, names.join(", "));
+            process::exit(1);
+        }
+    }
+}
 
-    let result = match day {
-        1 => {
-            if !is_second_test {
-                days::day_1::star_one(file_contents)
-            }
-            else {
-                days::day_1::star_two(file_contents)
-            }
-        },
-        2 => {
-            if !is_second_test {
-                if opt.other_impl {
-                    days::day_2_regex_impl::star_one(file_contents)
-                }
-                else {
-                    days::day_2::star_one(file_contents)
-                }
-            }
-            else {
-                if opt.other_impl {
-                    days::day_2_regex_impl::star_two(file_contents)
-                }
-                else {
-                    days::day_2::star_two(file_contents)
-                }
-            }
-        },
-        3 => {
-            if !is_second_test {
-                days::day_3::star_one(file_contents)
-            }
-            else {
-                days::day_3::star_two(file_contents)
-            }
-        },
-        4 => {
-            if !is_second_test {
-                days::day_4::star_one(file_contents)
-            }
-            else {
-                days::day_4::star_two(file_contents)
-            }
-        },
-        5 => {
-            if !is_second_test {
-                days::day_5::star_one(file_contents)
-            }
-            else {
-                days::day_5::star_two(file_contents)
-            }
-        },
-        6 => {
-            if !is_second_test {
-                days::day_6::star_one(file_contents)
-            }
-            else {
-                days::day_6::star_two(file_contents)
-            }
-        },
-        7 => {
-            if !is_second_test {
-                days::day_7::star_one(file_contents)
-            }
-            else {
-                days::day_7::star_two(file_contents)
-            }
-        },
-        8 => {
-            if !is_second_test {
-                days::day_8::star_one(file_contents)
-            }
-            else {
-                days::day_8::star_two(file_contents)
-            }
-        },
-        9 => {
-            if !is_second_test {
-                days::day_9::star_one(file_contents)
-            }
-            else {
-                days::day_9::star_two(file_contents)
-            }
-        },
-        10 => {
-            if !is_second_test {
-                days::day_10::star_one(file_contents)
-            }
-            else {
-                days::day_10::star_two(file_contents)
-            }
-        },
-        11 => {
-            if !is_second_test {
-                days::day_11::star_one(file_contents)
-            }
-            else {
-                days::day_11::star_two(file_contents)
-            }
-        },
-        12 => {
-            if !is_second_test {
-                days::day_12::star_one(file_contents)
-            }
-            else {
-                days::day_12::star_two(file_contents)
-            }
-        },
-        13 => {
-            if !is_second_test {
-                days::day_13::star_one(file_contents)
-            }
-            else {
-                days::day_13::star_two(file_contents)
-            }
-        },
-        14 => {
-            if !is_second_test {
-                days::day_14::star_one(file_contents)
-            }
-            else {
-                days::day_14::star_two(file_contents)
-            }
-        },
-        15 => {
-            if !is_second_test {
-                days::day_15::star_one(file_contents)
-            }
-            else {
-                days::day_15::star_two(file_contents)
-            }
-        },
-        16 => {
-            if !is_second_test {
-                days::day_16::star_one(file_contents)
-            }
-            else {
-                days::day_16::star_two(file_contents)
-            }
-        },
-        17 => {
-            if !is_second_test {
-                days::day_17::star_one(file_contents)
-            }
-            else {
-                days::day_17::star_two(file_contents)
-            }
-        },
-        18 => {
-            if !is_second_test {
-                days::day_18::star_one(file_contents)
-            }
-            else {
-                days::day_18::star_two(file_contents)
-            }
-        },
-        19 => {
-            if !is_second_test {
-                days::day_19::star_one(file_contents)
-            }
-            else {
-                days::day_19::star_two(file_contents)
-            }
-        },
-        20 => {
-            if !is_second_test {
-                days::day_20::star_one(file_contents)
-            }
-            else {
-                days::day_20::star_two(file_contents)
-            }
-        },
-        21 => {
-            if !is_second_test {
-                days::day_21::star_one(file_contents)
-            }
-            else {
-                days::day_21::star_two(file_contents)
-            }
+// Like the history, a broken cache shouldn't fail the run, it just doesn't get used
+fn load_cache (opt: &Opt) -> Option<Cache> {
+    if !opt.cache {
+        return None;
+    }
+    match Cache::load(Path::new(&opt.cache_file)) {
+        Ok(cache) => Some(cache),
+        Err(err) => {
+            log::emit(Level::Warn, format_args!("{}", err));
+            None
+        }
+    }
+}
+
+// The entry is only built with a cache to put it in, building one hashes the whole executable
+fn store_answer (cache: Option<&mut Cache>, entry: impl FnOnce() -> CacheEntry) {
+    if let Some(cache) = cache
+        && let Err(err) = cache.insert(entry()) {
+        log::emit(Level::Warn, format_args!("Could not cache the answer in '{}': {}", cache.path().display(), err));
+    }
+}
+
+// Runs one star of one day and prints the result, returning the exit code
+fn run_single (opt: &Opt, solution: &'static dyn Solution, part: Part, source: &InputSource) -> i32 {
+    let day = solution.day();
+    let file_read_result = source.read(Path::new(&opt.input_folder), day);
+    let file_contents = match (file_read_result, source.path(Path::new(&opt.input_folder), day)) {
+        (Ok(contents), _) => contents,
+        (Err(err), Some(path)) => {
+            eprintln!("An error occurred while reading the data file at '{}': {}", path.display(), err);
+            return 1;
         },
-        22 => {
-            if !is_second_test {
-                days::day_22::star_one(file_contents)
-            }
-            else {
-                days::day_22::star_two(file_contents)
-            }
+        (Err(err), None) => {
+            eprintln!("An error occurred while reading the puzzle input from stdin: {}", err);
+            return 1;
         },
-        23 => {
-            if !is_second_test {
-                days::day_23::star_one(file_contents)
-            }
-            else {
-                days::day_23::star_two(file_contents)
+    };
+
+    let params = Params::new(&opt.params).for_data_set(source.data_set());
+    let history_file = (!opt.no_history && params.is_empty()).then(|| Path::new(&opt.history_file));
+
+    let mut cache = load_cache(opt);
+    let cached = cache.as_ref()
+        .filter(| _ | !opt.no_cache)
+        .and_then(| cache | cache.get(day, part, solution.name(), file_contents.raw(), &params));
+    if let Some(entry) = cached {
+        if opt.format != Format::Text {
+            let record = Record {
+                cached: true,
+                ..Record::from(day, part, solution.name(), &source.to_string(), &Outcome::Solved(entry.answer.clone()), Some(entry.elapsed()), None)
+            };
+            if let Some(rendered) = output::render(opt.format, &[ record ]) {
+                println!("{}", rendered);
             }
+        }
+        else if opt.quiet {
+            println!("{}", entry.answer);
+        }
+        else {
+            println!("Result (cached): \n{}", entry.answer);
+            println!("Elapsed: {} (when it was solved)", format_duration(entry.elapsed()));
+        }
+        return 0;
+    }
+
+    if opt.format != Format::Text {
+        // Panics and errors become the record's status instead of going to stderr
+        let (outcome, elapsed, alloc_stats, phases) = {
+            let _quiet = runner::quiet_panics();
+            runner::run_with_timeout(solution, part, &file_contents, &params, opt.timeout)
+        };
+
+        if let Outcome::Solved(answer) = &outcome {
+            record_history(history_file, &[ HistoryEntry::new(day, part, solution.name(), file_contents.raw(), elapsed, 1, "run") ]);
+            store_answer(cache.as_mut(), || CacheEntry::new(day, part, solution.name(), file_contents.raw(), &params, answer, elapsed));
+        }
+
+        let record = Record::from(day, part, solution.name(), &source.to_string(), &outcome, Some(elapsed), alloc_stats).with_phases(elapsed, &phases);
+        if let Some(rendered) = output::render(opt.format, &[ record ]) {
+            println!("{}", rendered);
+        }
+        return if outcome.status() == "solved" { 0 } else { 1 };
+    }
+
+    // Solutions only borrow the input, so it's still here afterwards for errors to show the line they happened on
+    // Panics still go through the default hook, so they print the same as they always have
+    let (outcome, elapsed, alloc_stats, phases) = runner::run_with_timeout(solution, part, &file_contents, &params, opt.timeout);
+    match outcome {
+        Outcome::Solved(result) => {
+            record_history(history_file, &[ HistoryEntry::new(day, part, solution.name(), file_contents.raw(), elapsed, 1, "run") ]);
+            store_answer(cache.as_mut(), || CacheEntry::new(day, part, solution.name(), file_contents.raw(), &params, &result, elapsed));
+            if opt.quiet {
+                println!("{result}");
+                return 0;
+            }
+            println!("Result: \n{result}");
+            println!("Elapsed: {}", format_duration(elapsed));
+            print_phases(elapsed, &phases);
+            if let Some(alloc_stats) = alloc_stats {
+                println!("Memory: {}", alloc_stats);
+            }
+            0
         },
-        24 => {
-            if !is_second_test {
-                days::day_24::star_one(file_contents)
-            }
-            else {
-                days::day_24::star_two(file_contents)
-            }
+        Outcome::Failed(err) => {
+            // Positions in the error are in the input the day saw
+            eprintln!("{}", err.render(file_contents.for_solution(solution)));
+            1
         },
-        25 => {
-            if !is_second_test {
-                days::day_25::star_one(file_contents)
-            }
-            else {
-                days::day_25::star_two(file_contents)
+        Outcome::Panicked(_) => 101,
+        other => {
+            eprintln!("Day {} star {}: {} (elapsed {})", day, part, other, format_duration(elapsed));
+            1
+        }
+    }
+}
+
+
+fn main() {
+    let matches = Opt::clap().get_matches();
+    let mut opt = Opt::from_clap(&matches);
+
+    let config_path = if opt.no_config { None } else { opt.config.clone().map(PathBuf::from).or_else(config::find) };
+    if let Some(config_path) = config_path {
+        let settings = match Config::load(&config_path) {
+            Ok(config) => config.settings(opt.day),
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        };
+        if let Err(err) = apply_config(&mut opt, &matches, settings) {
+            eprintln!("Invalid setting in '{}': {}", config_path.display(), err);
+            process::exit(1);
+        }
+    }
+    output::set_color(opt.color);
+    log::set_max_level(match (opt.quiet, opt.verbose) {
+        (true, _) => Level::Error,
+        (false, 0) => Level::Info,
+        (false, 1) => Level::Debug,
+        (false, _) => Level::Trace,
+    });
+
+    // `requires` guarantees a day, and --impl has a default value so the conflict is checked by hand
+    if opt.other_impl {
+        let day = opt.day.unwrap();
+        if matches.occurrences_of("implementation") > 0 {
+            eprintln!("--other can't be used with --impl");
+            process::exit(1);
+        }
+        log::emit(Level::Warn, format_args!("--other is deprecated, use --impl with a name from --list-impls instead"));
+        match solution::for_day(day).into_iter().find(| solution | solution.name() != solution::DEFAULT_IMPL) {
+            Some(solution) => opt.implementation = solution.name().to_string(),
+            None => {
+                eprintln!("Day {} has no other implementation", day);
+                process::exit(1);
             }
         }
-        _ => panic!("Unexpected value for --day: {}", day)
+    }
+
+    if opt.list {
+        list_solutions();
+        return;
+    }
+
+    let data_sets = if opt.both {
+        vec![ DataSet::Example, DataSet::Full ]
+    }
+    else if opt.example {
+        vec![ DataSet::Example ]
+    }
+    else {
+        vec![ DataSet::Full ]
     };
 
-    let elapsed = start.elapsed();
-    println!("Result: \n{result}");
-    println!("Elapsed: {}", format_duration(elapsed));
-}
+    let sources: Vec<InputSource> = match &opt.input {
+        Some(input) => vec![ InputSource::from_arg(input) ],
+        None => data_sets.iter().map(| data_set | InputSource::Data(*data_set)).collect(),
+    };
+
+    if opt.list_impls {
+        // `requires` guarantees a day
+        for solution in solution::for_day(opt.day.unwrap()) {
+            println!("{}", solution.name());
+        }
+        return;
+    }
+
+    // `conflicts_with` doesn't work on arguments with a default value, so --impl is checked by hand
+    if opt.all && opt.implementation != solution::DEFAULT_IMPL {
+        eprintln!("--impl can't be used with --all, not every day has the same implementations");
+        process::exit(1);
+    }
+
+    if opt.format != Format::Text && (opt.bench || opt.check || opt.cross_check || opt.report.is_some()) {
+        eprintln!("--format is only supported for single runs, --all, and --inputs");
+        process::exit(1);
+    }
+
+    if opt.history {
+        let history_file = Path::new(&opt.history_file);
+        let entries = match history::load(history_file) {
+            Ok(entries) => entries,
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        };
+        let trends = Trends::from(&entries, opt.day, opt.slower_than);
+        if trends.trends.is_empty() {
+            println!("No timing history in '{}' yet", history_file.display());
+            return;
+        }
+        println!("{}", trends.text());
+        if !trends.regressions().is_empty() {
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Runs with changed puzzle constants can't be compared with the ones in the history
+    let params = Params::new(&opt.params);
+    let history_file = (!opt.no_history && params.is_empty()).then(|| Path::new(&opt.history_file));
+    let threads = if opt.serial { 1 } else { opt.jobs.unwrap_or(pool::default_threads()).max(1) };
+
+    // `all` conflicts with `day`, and the only other way to get here without a day is --report
+    let days: Vec<u8> = match opt.day {
+        Some(day) => vec![ day ],
+        None => (1..=25).collect(),
+    };
+    if let Some(day) = opt.day {
+        // Fail early on a typo instead of silently running nothing
+        select_impl(day, &opt.implementation);
+    }
+
+    // Stars that fail are just reported as failed, only failing to write the report fails the run
+    if let Some(dir) = &opt.report {
+        let dir = Path::new(dir);
+        let report_opts = ReportOpts {
+            data_set: if opt.example { DataSet::Example } else { DataSet::Full },
+            warmup: opt.warmup,
+            iterations: opt.iterations,
+            timeout: opt.timeout.unwrap_or(report::DEFAULT_TIMEOUT),
+            redact: opt.redact,
+            appendices: opt.appendices,
+        };
+        let report = report::build(Path::new(&opt.input_folder), &days, &report_opts);
+        if let Err(err) = report.write(dir) {
+            eprintln!("Could not write the report to '{}': {}", dir.display(), err);
+            process::exit(1);
+        }
+        println!("Wrote {} and {}", dir.join("report.md").display(), dir.join("report.html").display());
+        return;
+    }
+
+    if opt.bench {
+        let bench_opts = BenchOpts { warmup: opt.warmup, iterations: opt.iterations, compare: opt.compare, timeout: opt.timeout };
+        let report = bench::run_bench(Path::new(&opt.input_folder), &days, &sources, &opt.implementation, &params, &bench_opts);
+        record_history(history_file, &report.history);
+        println!("{}", report.text());
+        return;
+    }
+
+    if opt.check {
+        let report = check::check(Path::new(&opt.input_folder), &days, &opt.implementation, threads, opt.timeout);
+        println!("{}", report.text());
+        if report.failed() > 0 {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if opt.cross_check {
+        let report = cross_check::cross_check(Path::new(&opt.input_folder), &days, &sources, &params, opt.timeout.unwrap_or(cross_check::DEFAULT_TIMEOUT));
+        println!("{}", report.text());
+        if !report.agrees() {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if opt.all {
+        let report = batch::run_all(Path::new(&opt.input_folder), &data_sets, threads, opt.timeout);
+        record_history(history_file, &report.history);
+        match output::render(opt.format, &report.records()) {
+            Some(rendered) => println!("{}", rendered),
+            None => println!("{}", report.text()),
+        }
+        return;
+    }
+
+    // `required_unless_one` guarantees a day when we're not listing or running everything
+    let day = opt.day.unwrap();
+    let is_second_test = opt.second;
 
+    let solution = select_impl(day, &opt.implementation);
+
+    if !solution.implemented() {
+        eprintln!("Day {} is not implemented yet", day);
+        process::exit(1);
+    }
+
+    // `sources` only ever has one entry outside of --all
+    let source = &sources[0];
+    let part = if is_second_test { Part::Two } else { Part::One };
+
+    if let Some(inputs) = &opt.inputs {
+        let dir = match inputs {
+            Some(dir) => PathBuf::from(dir),
+            None => runner::inputs_dir(Path::new(&opt.input_folder), day),
+        };
+        let report = match batch::run_inputs(&dir, solution, part, &params, threads, opt.timeout) {
+            Ok(report) => report,
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        };
+        record_history(history_file, &report.history);
+        match output::render(opt.format, &report.records()) {
+            Some(rendered) => println!("{}", rendered),
+            None => println!("{}", report.inputs_text(&dir)),
+        }
+        if report.failures() > 0 {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if opt.watch {
+        if *source == InputSource::Stdin {
+            eprintln!("--watch can't re-read stdin, use a file with --input instead");
+            process::exit(1);
+        }
+
+        let watch_opts = WatchOpts {
+            day_folder: Path::new(&opt.input_folder).join(day.to_string()),
+            input_file: opt.input.as_ref().map(PathBuf::from),
+            poll_interval: Duration::from_millis(500),
+        };
+        watch::watch(&watch_opts, || {
+            run_single(&opt, solution, part, source);
+        });
+    }
+
+    let exit_code = run_single(&opt, solution, part, source);
+    if exit_code != 0 {
+        process::exit(exit_code);
+    }
+}
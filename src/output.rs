@@ -0,0 +1,242 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use owo_colors::{OwoColorize, Style};
+use serde::{Serialize, Serializer};
+use crate::alloc::{self, AllocStats};
+use crate::answer::Answer;
+use crate::phase::{self, PhaseTime};
+use crate::runner::Outcome;
+use crate::solution::Part;
+
+// How results are printed
+// Text is the human friendly default, JSON and CSV are for feeding runs into other tooling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str (s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format \"{}\", expected one of text, json, or csv", s)),
+        }
+    }
+}
+
+// Whether PASS/FAIL markers and warnings are colored
+// Auto colors only when stdout is a terminal and NO_COLOR (https://no-color.org) isn't set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl std::str::FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str (s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!("Unknown color choice \"{}\", expected one of auto, always, or never", s)),
+        }
+    }
+}
+
+static COLOR: AtomicBool = AtomicBool::new(true);
+
+pub fn set_color (choice: ColorChoice) {
+    let color = match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal(),
+    };
+    COLOR.store(color, Ordering::Relaxed);
+}
+
+pub fn paint (text: &str, style: Style) -> String {
+    if COLOR.load(Ordering::Relaxed) {
+        text.style(style).to_string()
+    }
+    else {
+        String::from(text)
+    }
+}
+
+// Columns padded to their widest cell, with a separator line under the headers
+pub fn table (headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths = headers.iter().map(| header | header.chars().count()).collect::<Vec<usize>>();
+    for row in rows {
+        for (col, cell) in row.iter().enumerate() {
+            widths[col] = widths[col].max(cell.chars().count());
+        }
+    }
+
+    let line = | cells: Vec<String> | cells.join(" | ").trim_end().to_string();
+    let mut lines = vec![ line(headers.iter().enumerate().map(| (col, header) | format!("{:<width$}", header, width = widths[col])).collect()) ];
+    lines.push(widths.iter().map(| width | "-".repeat(*width)).collect::<Vec<String>>().join("-+-"));
+    for row in rows {
+        lines.push(line(row.iter().enumerate().map(| (col, cell) | format!("{:<width$}", cell, width = widths[col])).collect()));
+    }
+    lines.join("\n")
+}
+
+// Tables only get a memory column when the allocation counter is compiled in
+pub fn with_memory_column (mut headers: Vec<&str>) -> Vec<&str> {
+    if alloc::enabled() {
+        headers.push("Memory");
+    }
+    headers
+}
+
+pub fn memory_cell (alloc_stats: Option<AllocStats>) -> Vec<String> {
+    if !alloc::enabled() {
+        return vec![];
+    }
+    vec![ alloc_stats.map(| stats | stats.to_string()).unwrap_or(String::from("-")) ]
+}
+
+// One star of one day run once, flattened for serialization
+// Field names and status values are part of the output format, so only ever add to them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub implementation: String,
+    pub input: String,
+    pub status: &'static str,
+    pub answer: Option<String>,
+    pub elapsed_nanos: Option<u128>,
+    pub error: Option<String>,
+
+    // How many stars were running at once, elapsed is wall-clock so anything over 1 includes contention
+    pub jobs: usize,
+
+    // Only filled in when built with the `alloc-stats` feature
+    pub allocations: Option<u64>,
+    pub bytes_allocated: Option<u64>,
+    pub peak_bytes: Option<u64>,
+
+    // True when the answer came out of the --cache instead of a run, elapsed is then the original run's time
+    pub cached: bool,
+
+    // Elapsed split into parsing the input and solving it, only for days that time their parse (see `phase`)
+    pub parse_nanos: Option<u128>,
+    pub solve_nanos: Option<u128>,
+
+    // The answer as it was returned: "integer", "text", or "grid"
+    // JSON also gets it typed as `value`, CSV only has text so `answer` and `answer_type` have to do
+    pub answer_type: Option<&'static str>,
+    #[serde(serialize_with = "serialize_value")]
+    pub value: Option<Answer>,
+}
+
+fn serialize_value <S: Serializer> (value: &Option<Answer>, serializer: S) -> Result<S::Ok, S::Error> {
+    value.as_ref().map(Answer::plain).serialize(serializer)
+}
+
+impl Record {
+    pub fn from (day: u8, part: Part, implementation: &str, input: &str, outcome: &Outcome, elapsed: Option<Duration>, alloc_stats: Option<AllocStats>) -> Self {
+        let value = match outcome {
+            Outcome::Solved(answer) => Some(answer.clone()),
+            _ => None,
+        };
+        let (answer, error) = match outcome {
+            Outcome::Solved(answer) => (Some(answer.to_string()), None),
+            Outcome::NotImplemented => (None, None),
+            Outcome::MissingInput(message) => (None, Some(message.clone())),
+            Outcome::Failed(err) => (None, Some(err.to_string())),
+            Outcome::Panicked(message) => (None, Some(message.clone())),
+            Outcome::TimedOut(_) => (None, Some(outcome.to_string())),
+        };
+
+        Record {
+            day,
+            part: match part { Part::One => 1, Part::Two => 2 },
+            implementation: implementation.to_string(),
+            input: input.to_string(),
+            status: outcome.status(),
+            answer,
+            elapsed_nanos: elapsed.map(| elapsed | elapsed.as_nanos()),
+            error,
+            jobs: 1,
+            allocations: alloc_stats.map(| stats | stats.allocations),
+            bytes_allocated: alloc_stats.map(| stats | stats.bytes_allocated),
+            peak_bytes: alloc_stats.map(| stats | stats.peak_bytes),
+            cached: false,
+            parse_nanos: None,
+            solve_nanos: None,
+            answer_type: value.as_ref().map(Answer::kind),
+            value,
+        }
+    }
+
+    pub fn with_phases (self, elapsed: Duration, phases: &[PhaseTime]) -> Self {
+        let (parse, solve) = phase::split(elapsed, phases);
+        if parse.is_none() {
+            return self;
+        }
+        Record {
+            parse_nanos: parse.map(| parse | parse.as_nanos()),
+            solve_nanos: Some(solve.as_nanos()),
+            ..self
+        }
+    }
+}
+
+const CSV_HEADERS: [&str; 16] = [ "day", "part", "implementation", "input", "status", "answer", "elapsed_nanos", "error", "jobs", "allocations", "bytes_allocated", "peak_bytes", "cached", "parse_nanos", "solve_nanos", "answer_type" ];
+
+// Quotes a field only when it has to (RFC 4180), so plain answers stay plain
+fn csv_field (field: &str) -> String {
+    if field.contains([ ',', '"', '\n', '\r' ]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    }
+    else {
+        field.to_string()
+    }
+}
+
+// A JSON array of records, or a CSV file with a header row
+// Text output is table-shaped and differs between modes, so it's None and the caller prints that one itself
+pub fn render (format: Format, records: &[Record]) -> Option<String> {
+    let rendered = match format {
+        Format::Json => serde_json::to_string_pretty(records).expect("Records always serialize"),
+        Format::Csv => {
+            let mut lines = vec![ CSV_HEADERS.join(",") ];
+            for record in records {
+                let fields = [
+                    record.day.to_string(),
+                    record.part.to_string(),
+                    record.implementation.clone(),
+                    record.input.clone(),
+                    record.status.to_string(),
+                    record.answer.clone().unwrap_or_default(),
+                    record.elapsed_nanos.map(| nanos | nanos.to_string()).unwrap_or_default(),
+                    record.error.clone().unwrap_or_default(),
+                    record.jobs.to_string(),
+                    record.allocations.map(| count | count.to_string()).unwrap_or_default(),
+                    record.bytes_allocated.map(| bytes | bytes.to_string()).unwrap_or_default(),
+                    record.peak_bytes.map(| bytes | bytes.to_string()).unwrap_or_default(),
+                    record.cached.to_string(),
+                    record.parse_nanos.map(| nanos | nanos.to_string()).unwrap_or_default(),
+                    record.solve_nanos.map(| nanos | nanos.to_string()).unwrap_or_default(),
+                    record.answer_type.unwrap_or_default().to_string(),
+                ];
+                lines.push(fields.iter().map(| field | csv_field(field)).collect::<Vec<String>>().join(","));
+            }
+            lines.join("\n")
+        },
+        Format::Text => return None,
+    };
+    Some(rendered)
+}
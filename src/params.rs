@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use crate::error::{AocError, AocResult};
+use crate::runner::DataSet;
+
+// Puzzle constants that can be changed from the command line with `--param key=value`
+// Every day that has any declares a typed struct for them (like `Day1Params`) that reads its keys out of
+//      here, falling back to the puzzle's own values for anything that wasn't given
+// Some constants are different for the example (day 8 stops after 10 connections instead of 1000), so the
+//      runner also passes along which data set the input came from, when it knows
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+    example: bool,
+}
+
+impl Params {
+    // When a key is given more than once the last one wins
+    pub fn new (pairs: &[(String, String)]) -> Self {
+        Params {
+            values: pairs.iter().cloned().collect(),
+            example: false,
+        }
+    }
+
+    // Inputs read from stdin, or from a file not named example.txt, could be anything, so they get the full puzzle's defaults
+    pub fn for_data_set (&self, data_set: Option<DataSet>) -> Self {
+        Params {
+            values: self.values.clone(),
+            example: data_set == Some(DataSet::Example),
+        }
+    }
+
+    pub fn example (&self) -> bool {
+        self.example
+    }
+
+    pub fn is_empty (&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // Looks up `key`, or `default` when it wasn't given
+    pub fn get<T> (&self, key: &str, default: T) -> AocResult<T>
+    where
+        T: FromStr,
+        T::Err: Display
+    {
+        match self.values.get(key) {
+            None => Ok(default),
+            Some(value) => value.parse::<T>()
+                .map_err(| err | AocError::new(format!("Invalid value '{}' for parameter '{}': {}", value, key, err))),
+        }
+    }
+
+    // A typo in a key would otherwise silently run with the default
+    pub fn expect_keys (&self, known: &[&str]) -> AocResult<()> {
+        let Some(unknown) = self.values.keys().find(| key | !known.contains(&key.as_str())) else {
+            return Ok(());
+        };
+
+        if known.is_empty() {
+            Err(AocError::new(format!("Unknown parameter '{}', this day doesn't take any", unknown)))
+        }
+        else {
+            Err(AocError::new(format!("Unknown parameter '{}', this day takes: {}", unknown, known.join(", "))))
+        }
+    }
+}
+
+impl std::fmt::Display for Params {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pairs: Vec<String> = self.values.iter()
+            .map(| (key, value) | format!("{}={}", key, value))
+            .collect();
+        write!(f, "{}", pairs.join(","))
+    }
+}
+
+// For structopt, `--param dial_size=120`
+pub fn parse_param (arg: &str) -> Result<(String, String), String> {
+    let Some((key, value)) = arg.split_once('=') else {
+        return Err(format!("Expected a parameter like 'key=value', but got '{}'", arg));
+    };
+
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(format!("Parameter '{}' is missing a key", arg));
+    }
+    Ok((String::from(key), String::from(value.trim())))
+}
@@ -0,0 +1,103 @@
+// Timers for the phases of a solution, so a slow parse (like day 8 building its distance heap) can be told
+//      apart from the actual solving
+//
+// Days wrap their parsing in `parse` and anything else worth timing on its own in `phase`, phases can nest:
+//
+//      let mut graph = phase::parse(|| Graph::from(input))?;
+//
+//      // Inside Graph::from, shows up as parse > distances
+//      let (distance_matrix, dqueue) = phase::phase("distances", || Graph::distances(&points));
+//
+// The runner collects them with `measure`, everything outside of the top level "parse" phase counts as solving
+// Keep phases coarse, timing every line of the input costs more than parsing it, so parse it all up front in one phase
+// Like the allocation counters these are per thread, and phases outside of a `measure` aren't recorded at all
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+pub const PARSE: &str = "parse";
+
+// All the time spent in one phase, a phase that runs more than once (like one inside a loop) is summed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseTime {
+    // Names of the phases it's nested in, then its own
+    pub path: Vec<&'static str>,
+    pub elapsed: Duration,
+    pub count: u32,
+}
+
+impl PhaseTime {
+    pub fn name (&self) -> &'static str {
+        self.path.last().copied().unwrap_or("")
+    }
+
+    pub fn depth (&self) -> usize {
+        self.path.len() - 1
+    }
+}
+
+thread_local! {
+    static MEASURING: Cell<bool> = const { Cell::new(false) };
+    static STACK: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+
+    // In the order the phases first started, so every phase comes before the ones nested in it
+    static PHASES: RefCell<Vec<PhaseTime>> = const { RefCell::new(Vec::new()) };
+}
+
+pub fn phase <T> (name: &'static str, run: impl FnOnce() -> T) -> T {
+    if !MEASURING.with(Cell::get) {
+        return run();
+    }
+
+    // Only a phase's first run allocates, so phases inside a loop don't skew the allocation stats
+    let index = STACK.with(| stack | {
+        let mut stack = stack.borrow_mut();
+        stack.push(name);
+        PHASES.with(| phases | {
+            let mut phases = phases.borrow_mut();
+            match phases.iter().position(| phase | phase.path == *stack) {
+                Some(index) => index,
+                None => {
+                    phases.push(PhaseTime { path: stack.clone(), elapsed: Duration::ZERO, count: 0 });
+                    phases.len() - 1
+                }
+            }
+        })
+    });
+
+    let start = Instant::now();
+    let result = run();
+    let elapsed = start.elapsed();
+
+    PHASES.with(| phases | {
+        let phase = &mut phases.borrow_mut()[index];
+        phase.elapsed += elapsed;
+        phase.count += 1;
+    });
+    STACK.with(| stack | stack.borrow_mut().pop());
+    result
+}
+
+pub fn parse <T> (run: impl FnOnce() -> T) -> T {
+    phase(PARSE, run)
+}
+
+// Runs `run` and reports the phases it went through on this thread
+pub fn measure <T> (run: impl FnOnce() -> T) -> (T, Vec<PhaseTime>) {
+    // A panic in an earlier run can leave phases behind that never finished
+    STACK.with(| stack | stack.borrow_mut().clear());
+    PHASES.with(| phases | phases.borrow_mut().clear());
+
+    MEASURING.with(| measuring | measuring.set(true));
+    let result = run();
+    MEASURING.with(| measuring | measuring.set(false));
+
+    (result, PHASES.with(| phases | phases.take()))
+}
+
+// Splits a run's total time into parsing and solving, None for parsing when the day never said what its parse was
+pub fn split (total: Duration, phases: &[PhaseTime]) -> (Option<Duration>, Duration) {
+    let parse = phases.iter()
+        .find(| phase | phase.path == [ PARSE ])
+        .map(| phase | phase.elapsed);
+    (parse, total.saturating_sub(parse.unwrap_or(Duration::ZERO)))
+}
@@ -0,0 +1,46 @@
+// A tiny scoped thread pool for batch runs
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use crate::runner::WORKER_STACK_SIZE;
+
+// How many threads to use when nobody says otherwise
+pub fn default_threads () -> usize {
+    thread::available_parallelism().map(| threads | threads.get()).unwrap_or(1)
+}
+
+// Maps `work` over `items` on up to `threads` threads, with the results in the same order as `items`
+// Threads pull the next item whenever they finish one, so a slow day doesn't hold up a whole share of the others
+pub fn map <T: Send, R: Send> (items: Vec<T>, threads: usize, work: impl Fn(T) -> R + Sync) -> Vec<R> {
+    let count = items.len();
+    if threads <= 1 || count <= 1 {
+        return items.into_iter().map(work).collect();
+    }
+
+    let items = items.into_iter().map(| item | Mutex::new(Some(item))).collect::<Vec<_>>();
+    let results = (0..count).map(| _ | Mutex::new(None)).collect::<Vec<Mutex<Option<R>>>>();
+    let next = AtomicUsize::new(0);
+
+    thread::scope(| scope | {
+        for worker in 0..threads.min(count) {
+            thread::Builder::new()
+                .name(format!("batch-{}", worker))
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, || loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    if idx >= count {
+                        break;
+                    }
+
+                    let item = items[idx].lock().unwrap().take().expect("Every item is only taken once");
+                    let result = work(item);
+                    *results[idx].lock().unwrap() = Some(result);
+                })
+                .expect("Could not spawn a batch worker thread");
+        }
+    });
+
+    results.into_iter()
+        .map(| result | result.into_inner().unwrap().expect("Every item has a result once the scope ends"))
+        .collect()
+}
@@ -0,0 +1,249 @@
+use std::{fs, io, panic, path::Path};
+use std::time::Duration;
+use crate::alloc::{self, AllocStats};
+use crate::bench::{self, Stats};
+use crate::days::APPENDICES;
+use crate::error::AocResult;
+use crate::history;
+use crate::params::Params;
+use crate::runner::{self, DataSet, Outcome, format_duration};
+use crate::solution::{self, Part};
+
+// A results page for the whole calendar, written by --report as both Markdown (for the README) and HTML
+// Every row is one star of one implementation, timed the same way --bench does it
+// Answers can be redacted, since puzzle answers aren't meant to be published
+
+// A rendering of a day's input for the report's appendices, see `days::APPENDICES`
+pub struct Appendix {
+    pub day: u8,
+    pub title: &'static str,
+    pub render: fn(&str) -> AocResult<String>,
+}
+
+pub struct ReportRow {
+    pub day: u8,
+    pub part: Part,
+    pub implementation: &'static str,
+
+    // The answer, or why there isn't one
+    pub result: Result<String, String>,
+    pub median: Option<Duration>,
+    pub alloc_stats: Option<AllocStats>,
+}
+
+pub struct RenderedAppendix {
+    pub day: u8,
+    pub title: &'static str,
+    pub body: Result<String, String>,
+}
+
+pub struct Report {
+    pub data_set: String,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub git_rev: Option<String>,
+    pub redact: bool,
+    pub rows: Vec<ReportRow>,
+    pub appendices: Vec<RenderedAppendix>,
+}
+
+// How long a report gives each run of a star when there's no --timeout
+// Every implementation gets benched, including ones that are slow on purpose (day 7's naive one), so a report
+//      always has a limit, and a star that hits it is reported as timed out like any other failure
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct ReportOpts {
+    pub data_set: DataSet,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub timeout: Duration,
+    pub redact: bool,
+
+    // Add renderings of the inputs of days that have one
+    pub appendices: bool,
+}
+
+// Benches every implementation of each implemented day of `days`, stars that fail are just reported as failed
+pub fn build (data_folder_root: &Path, days: &[u8], report_opts: &ReportOpts) -> Report {
+    let params = Params::default().for_data_set(Some(report_opts.data_set));
+
+    let _quiet = runner::quiet_panics();
+    let mut rows: Vec<ReportRow> = Vec::new();
+    let mut appendices: Vec<RenderedAppendix> = Vec::new();
+    for day in days {
+        let day = *day;
+        let impls = solution::for_day(day);
+        if !impls.iter().any(| solution | solution.implemented()) {
+            continue;
+        }
+
+        let input = runner::read_input(&runner::data_path(data_folder_root, day, report_opts.data_set)).map_err(| err | err.to_string());
+        for solution in &impls {
+            for part in [ Part::One, Part::Two ] {
+                let (result, median, alloc_stats) = match &input {
+                    Err(err) => (Err(Outcome::MissingInput(err.clone()).to_string()), None, None),
+                    Ok(input) => match bench::bench(*solution, part, input, &params, report_opts.warmup, report_opts.iterations, Some(report_opts.timeout)) {
+                        Ok(result) => (Ok(result.answer.to_string()), Some(Stats::from(&result.samples).median), result.alloc_stats),
+                        Err(outcome) => (Err(outcome.to_string()), None, None),
+                    },
+                };
+                rows.push(ReportRow { day, part, implementation: solution.name(), result, median, alloc_stats });
+            }
+        }
+
+        if let (true, Ok(input)) = (report_opts.appendices, &input) {
+            // Renderers get the same cleaned up input the stars do
+            for appendix in APPENDICES.iter().filter(| appendix | appendix.day == day) {
+                let body = panic::catch_unwind(|| (appendix.render)(input.normalized()))
+                    .map_err(|_| String::from("the renderer panicked"))
+                    .and_then(| body | body.map_err(| err | err.to_string()));
+                appendices.push(RenderedAppendix { day, title: appendix.title, body });
+            }
+        }
+    }
+
+    Report {
+        data_set: report_opts.data_set.to_string(),
+        warmup: report_opts.warmup,
+        iterations: report_opts.iterations,
+        git_rev: history::git_rev(),
+        redact: report_opts.redact,
+        rows,
+        appendices,
+    }
+}
+
+impl Report {
+    // Writes report.md and report.html into `dir`, creating it if needed
+    pub fn write (&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("report.md"), self.markdown())?;
+        fs::write(dir.join("report.html"), self.html())
+    }
+
+    fn summary (&self) -> String {
+        let rev = match &self.git_rev {
+            Some(rev) => format!(" at {}", rev),
+            None => String::new(),
+        };
+        format!(
+            "Answers for the {} inputs{}, times are the median of {} runs after {} warm-up runs.",
+            self.data_set, rev, self.iterations, self.warmup
+        )
+    }
+
+    // Tables only get a memory column when the allocation counter is compiled in
+    fn headers (&self) -> Vec<&'static str> {
+        let mut headers = vec![ "Day", "Part", "Impl", "Answer", "Median" ];
+        if alloc::enabled() {
+            headers.push("Memory");
+        }
+        headers
+    }
+
+    fn cells (&self, row: &ReportRow) -> Vec<String> {
+        let answer = match &row.result {
+            Ok(_) if self.redact => String::from("redacted"),
+            Ok(answer) => answer.clone(),
+            Err(reason) => reason.clone(),
+        };
+
+        let mut cells = vec![
+            row.day.to_string(),
+            row.part.to_string(),
+            row.implementation.to_string(),
+            answer,
+            row.median.map(format_duration).unwrap_or(String::from("-")),
+        ];
+        if alloc::enabled() {
+            cells.push(row.alloc_stats.map(| stats | stats.to_string()).unwrap_or(String::from("-")));
+        }
+        cells
+    }
+
+    pub fn markdown (&self) -> String {
+        let escape = | cell: &str | cell.replace('|', "\\|").replace('\n', "<br>");
+
+        let headers = self.headers();
+        let mut lines = vec![
+            String::from("# Advent of Code 2025"),
+            String::new(),
+            self.summary(),
+            String::new(),
+            format!("| {} |", headers.join(" | ")),
+            format!("|{}|", headers.iter().map(| _ | "---").collect::<Vec<&str>>().join("|")),
+        ];
+        for row in &self.rows {
+            let cells = self.cells(row).iter().map(| cell | escape(cell)).collect::<Vec<String>>();
+            lines.push(format!("| {} |", cells.join(" | ")));
+        }
+
+        for appendix in &self.appendices {
+            lines.push(String::new());
+            lines.push(format!("## Day {}: {}", appendix.day, appendix.title));
+            lines.push(String::new());
+            match &appendix.body {
+                Ok(body) => {
+                    lines.push(String::from("```text"));
+                    lines.push(body.trim_end().to_string());
+                    lines.push(String::from("```"));
+                },
+                Err(err) => lines.push(format!("*Could not render: {}*", escape(err))),
+            }
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    pub fn html (&self) -> String {
+        let mut html = String::from(concat!(
+            "<!DOCTYPE html>\n",
+            "<html lang=\"en\">\n",
+            "<head>\n",
+            "<meta charset=\"utf-8\">\n",
+            "<title>Advent of Code 2025</title>\n",
+            "<style>\n",
+            "body { font-family: sans-serif; margin: 2em; }\n",
+            "table { border-collapse: collapse; }\n",
+            "th, td { border: 1px solid #ccc; padding: 0.25em 0.75em; text-align: left; }\n",
+            "pre { background: #f4f4f4; padding: 1em; overflow-x: auto; }\n",
+            "</style>\n",
+            "</head>\n",
+            "<body>\n",
+            "<h1>Advent of Code 2025</h1>\n",
+        ));
+        html += &format!("<p>{}</p>\n", escape_html(&self.summary()));
+
+        html += "<table>\n<thead>\n<tr>";
+        for header in self.headers() {
+            html += &format!("<th>{}</th>", header);
+        }
+        html += "</tr>\n</thead>\n<tbody>\n";
+        for row in &self.rows {
+            html += "<tr>";
+            for cell in self.cells(row) {
+                html += &format!("<td>{}</td>", escape_html(&cell).replace('\n', "<br>"));
+            }
+            html += "</tr>\n";
+        }
+        html += "</tbody>\n</table>\n";
+
+        for appendix in &self.appendices {
+            html += &format!("<h2>Day {}: {}</h2>\n", appendix.day, escape_html(appendix.title));
+            match &appendix.body {
+                Ok(body) => html += &format!("<pre>{}</pre>\n", escape_html(body.trim_end())),
+                Err(err) => html += &format!("<p><em>Could not render: {}</em></p>\n", escape_html(err)),
+            }
+        }
+
+        html += "</body>\n</html>\n";
+        html
+    }
+}
+
+fn escape_html (text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
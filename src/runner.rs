@@ -0,0 +1,294 @@
+use std::{any::Any, fs, io::{self, Read}, panic::{self, AssertUnwindSafe}, path::{Path, PathBuf}};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::alloc::{self, AllocStats};
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::input::Input;
+use crate::log;
+use crate::params::Params;
+use crate::phase::{self, PhaseTime};
+use crate::solution::{Part, Solution};
+
+// Which of the two data files in a day's folder to read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataSet {
+    Example,
+    Full
+}
+
+impl DataSet {
+    pub fn file_name (&self) -> &'static str {
+        match self {
+            DataSet::Example => "example.txt",
+            DataSet::Full => "full.txt",
+        }
+    }
+
+    // Files named like the data folder's are taken to be that data set, wherever they are
+    pub fn from_path (path: &Path) -> Option<Self> {
+        [ DataSet::Example, DataSet::Full ].into_iter()
+            .find(| data_set | path.file_name().is_some_and(| name | name == data_set.file_name()))
+    }
+}
+
+impl std::fmt::Display for DataSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataSet::Example => write!(f, "example"),
+            DataSet::Full => write!(f, "full"),
+        }
+    }
+}
+
+// <data_folder_root>/<day>/{example,full}.txt
+pub fn data_path (data_folder_root: &Path, day: u8, data_set: DataSet) -> PathBuf {
+    data_folder_root.join(day.to_string()).join(data_set.file_name())
+}
+
+// Other people's puzzle inputs for a day live in <data_folder_root>/<day>/inputs/*.txt
+pub fn inputs_dir (data_folder_root: &Path, day: u8) -> PathBuf {
+    data_folder_root.join(day.to_string()).join("inputs")
+}
+
+// Every .txt file directly in `dir`, sorted by name so batch runs always come out in the same order
+pub fn list_inputs (dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut inputs = fs::read_dir(dir)?
+        .map(| entry | entry.map(| entry | entry.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()?;
+    inputs.retain(| path | path.is_file() && path.extension().is_some_and(| extension | extension == "txt"));
+    inputs.sort();
+    Ok(inputs)
+}
+
+pub fn read_input (path: &Path) -> io::Result<Input> {
+    fs::read_to_string(path).map(Input::from)
+}
+
+// Where a day's puzzle input comes from
+// The data folder convention is the default, but any file (or stdin) can be used for hand-made edge cases
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Data(DataSet),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    // "-" means stdin, like most command line tools
+    pub fn from_arg (arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        }
+        else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn path (&self, data_folder_root: &Path, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Data(data_set) => Some(data_path(data_folder_root, day, *data_set)),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    // Which data set the input is, when it comes from the data folder or a file named like one of its files
+    pub fn data_set (&self) -> Option<DataSet> {
+        match self {
+            InputSource::Data(data_set) => Some(*data_set),
+            InputSource::File(path) => DataSet::from_path(path),
+            InputSource::Stdin => None,
+        }
+    }
+
+    pub fn read (&self, data_folder_root: &Path, day: u8) -> io::Result<Input> {
+        match self.path(data_folder_root, day) {
+            Some(path) => read_input(&path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(Input::from(input))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Data(data_set) => write!(f, "{}", data_set),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(Answer),
+    NotImplemented,
+    MissingInput(String),
+    Failed(AocError),
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl Outcome {
+    // Short machine-readable name, used as the status of JSON and CSV records
+    pub fn status (&self) -> &'static str {
+        match self {
+            Outcome::Solved(_) => "solved",
+            Outcome::NotImplemented => "not_implemented",
+            Outcome::MissingInput(_) => "missing_input",
+            Outcome::Failed(_) => "failed",
+            Outcome::Panicked(_) => "panicked",
+            Outcome::TimedOut(_) => "timed_out",
+        }
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Solved(answer) => write!(f, "{}", answer),
+            Outcome::NotImplemented => write!(f, "not implemented"),
+            Outcome::MissingInput(_) => write!(f, "missing input"),
+            Outcome::Failed(err) => write!(f, "error: {}", err),
+            Outcome::Panicked(message) => write!(f, "panicked: {}", message),
+            Outcome::TimedOut(limit) => write!(f, "timed out after {}", format_duration(*limit)),
+        }
+    }
+}
+
+// What one run of a star came to, how long it took, what it allocated, and the phases it went through
+pub type RunResult = (Outcome, Duration, Option<AllocStats>, Vec<PhaseTime>);
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+// Keeps panics off stderr for as long as it's alive, for runs that report panics themselves (in a table, a
+//      record, ...), and puts back whatever hook was there before when it's dropped
+//
+//      let _quiet = runner::quiet_panics();
+pub struct QuietPanics {
+    previous: Option<PanicHook>,
+}
+
+pub fn quiet_panics () -> QuietPanics {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(| _ | {}));
+    QuietPanics { previous: Some(previous) }
+}
+
+impl Drop for QuietPanics {
+    fn drop (&mut self) {
+        // The hook can't be changed while this thread is panicking, the process is going down anyway
+        if let (Some(previous), false) = (self.previous.take(), thread::panicking()) {
+            panic::set_hook(previous);
+        }
+    }
+}
+
+fn panic_message (payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    }
+    else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    }
+    else {
+        String::from("<non-string panic payload>")
+    }
+}
+
+// Runs one star and times it, catching any panic so a single broken day can't take down a batch of runs
+// A `todo!()` (a day where only the first star is done, for example) is reported as not implemented
+// Bad input is reported through the error the day returned, panics are kept for actual bugs
+// Allocation stats are only there when built with the `alloc-stats` feature
+// The day gets the input normalized when it was loaded (see `input::Input`), unless it asked for it raw
+// Days only ever borrow the input, so one loaded input can be run any number of times without copying it
+// Phases are whatever the day timed on its own (see `phase`), `phase::split` turns them into parse and solve times
+pub fn run_caught (solution: &dyn Solution, part: Part, input: &Input, params: &Params) -> RunResult {
+    let input = input.for_solution(solution);
+    log::set_star(Some((solution.day(), part)));
+    let (((result, phases), elapsed), alloc_stats) = alloc::measure(|| {
+        let start = Instant::now();
+        let result = phase::measure(|| panic::catch_unwind(AssertUnwindSafe(|| solution.run(part, input, params))));
+        (result, start.elapsed())
+    });
+    log::set_star(None);
+
+    let outcome = match result {
+        Ok(Ok(answer)) => Outcome::Solved(answer),
+        Ok(Err(err)) => Outcome::Failed(err),
+        Err(payload) => {
+            let message = panic_message(payload.as_ref());
+            if message == "not yet implemented" {
+                Outcome::NotImplemented
+            }
+            else {
+                Outcome::Panicked(message)
+            }
+        }
+    };
+    (outcome, elapsed, alloc_stats, phases)
+}
+
+// Same as the main thread's stack on Linux, so deep recursion behaves the same with or without a timeout
+pub(crate) const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+// `run_caught`, but gives up waiting after `timeout` (if there is one) and reports the star as timed out
+// Threads can't be killed, so a timed out solver keeps running in the background until the process exits
+// The input is shared with that thread rather than copied, cloning an `Input` only clones its `Arc`s
+pub fn run_with_timeout (solution: &'static dyn Solution, part: Part, input: &Input, params: &Params, timeout: Option<Duration>) -> RunResult {
+    let Some(timeout) = timeout else {
+        return run_caught(solution, part, input, params);
+    };
+
+    let input = input.clone();
+    let params = params.clone();
+    let (sender, receiver) = mpsc::channel();
+    let start = Instant::now();
+    let spawned = thread::Builder::new()
+        .name(format!("day-{}-star-{}", solution.day(), part))
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            // The receiver is gone if we already timed out, nobody is left to tell
+            let _ = sender.send(run_caught(solution, part, &input, &params));
+        });
+    if let Err(err) = spawned {
+        return (Outcome::Panicked(format!("could not spawn a worker thread: {}", err)), start.elapsed(), None, vec![]);
+    }
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => (Outcome::TimedOut(timeout), start.elapsed(), None, vec![]),
+        Err(RecvTimeoutError::Disconnected) => (Outcome::Panicked(String::from("worker thread exited without a result")), start.elapsed(), None, vec![]),
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let total_nanos = duration.as_nanos();
+
+    let hours = (total_nanos / (1_000_000_000 * 60 * 60)) % 24;
+    let minutes = (total_nanos / (1_000_000_000 * 60)) % 60;
+    let seconds = (total_nanos / 1_000_000_000) % 60;
+
+    let nanoseconds = total_nanos % 1_000_000_000;
+
+    // Add commas to the nano seconds
+    // Maybe there's a better way to do this, but for now I'm just reversing the string, replacing every three pairs
+    //      of digits with those same digits plus a comma, then reversing again
+    let nano_str = nanoseconds.to_string().chars().rev().collect::<String>();
+    let reg = regex::Regex::new(r"(\d{3})").unwrap();
+    let nano_comma_str= reg.replace_all(&nano_str, "$1,").chars().rev().collect::<String>();
+    let nano_comma_str = if let Some(stripped) = nano_comma_str.strip_prefix(",") {
+        stripped
+    }
+    else {
+        &nano_comma_str
+    };
+
+    format!("{:02}:{:02}:{:02} {} nanos", hours, minutes, seconds, nano_comma_str)
+}
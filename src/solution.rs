@@ -0,0 +1,132 @@
+use crate::answer::Answer;
+use crate::days::SOLUTIONS;
+use crate::error::AocResult;
+use crate::params::Params;
+
+// Which of the two puzzles for a day to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+// Every day module registers one `Solution` per implementation with the `solution!` macro below
+// Days with more than one implementation (like day 2 and its regex version) register each one under
+//      a different name, and the runner looks them all up through `SOLUTIONS` in `days/mod.rs`
+// Stars borrow their input, so the runner can hand the same loaded input to both stars, every implementation,
+//      and every bench iteration, days that would rather work on bytes can use `input.as_bytes()` for free
+pub trait Solution: Sync {
+    fn day (&self) -> u8;
+    fn name (&self) -> &'static str;
+    fn star_one (&self, input: &str, params: &Params) -> AocResult<Answer>;
+    fn star_two (&self, input: &str, params: &Params) -> AocResult<Answer>;
+
+    // False for the `todo!()` stubs of days that haven't been solved yet
+    fn implemented (&self) -> bool {
+        true
+    }
+
+    // True for days that want their input exactly as it was read, instead of put through `input::normalize`
+    fn raw_input (&self) -> bool {
+        false
+    }
+
+    fn run (&self, part: Part, input: &str, params: &Params) -> AocResult<Answer> {
+        match part {
+            Part::One => self.star_one(input, params),
+            Part::Two => self.star_two(input, params),
+        }
+    }
+}
+
+// Implements `Solution` for a unit struct that forwards to the `star_one` and `star_two` functions of the
+//      module the macro is invoked in, tagging any error they return with the day
+// Alternative implementations that only replace one of the stars can name the functions to forward to
+// Days with puzzle constants that `--param` can change (see `params.rs`) are marked with `params`, and their star
+//      functions take the `&Params` as a second argument, every other day rejects any `--param` it's given
+//
+//      solution!(Day1, day = 1);
+//      solution!(Day2Regex, day = 2, name = "regex");
+//      solution!(Day7Naive, day = 7, name = "naive", star_one = star_one, star_two = star_two_naive);
+//      solution!(Day11, day = 11, unimplemented);
+//      solution!(Day12, day = 12, raw_input);
+//      solution!(Day1, day = 1, params);
+//      solution!(Day10Iterative, day = 10, name = "iterative", star_one = star_one_iterative, star_two = star_two, params);
+macro_rules! solution {
+    ($ident:ident, day = $day:literal) => {
+        $crate::solution::solution!($ident, day = $day, name = $crate::solution::DEFAULT_IMPL);
+    };
+    ($ident:ident, day = $day:literal, unimplemented) => {
+        $crate::solution::solution!($ident, day = $day, name = $crate::solution::DEFAULT_IMPL, star_one = star_one, star_two = star_two, implemented = false, raw_input = false, params = false);
+    };
+    ($ident:ident, day = $day:literal, raw_input) => {
+        $crate::solution::solution!($ident, day = $day, name = $crate::solution::DEFAULT_IMPL, star_one = star_one, star_two = star_two, implemented = true, raw_input = true, params = false);
+    };
+    ($ident:ident, day = $day:literal, params) => {
+        $crate::solution::solution!($ident, day = $day, name = $crate::solution::DEFAULT_IMPL, star_one = star_one, star_two = star_two, implemented = true, raw_input = false, params = true);
+    };
+    ($ident:ident, day = $day:literal, name = $name:expr) => {
+        $crate::solution::solution!($ident, day = $day, name = $name, star_one = star_one, star_two = star_two);
+    };
+    ($ident:ident, day = $day:literal, name = $name:expr, star_one = $star_one:ident, star_two = $star_two:ident) => {
+        $crate::solution::solution!($ident, day = $day, name = $name, star_one = $star_one, star_two = $star_two, implemented = true, raw_input = false, params = false);
+    };
+    ($ident:ident, day = $day:literal, name = $name:expr, star_one = $star_one:ident, star_two = $star_two:ident, params) => {
+        $crate::solution::solution!($ident, day = $day, name = $name, star_one = $star_one, star_two = $star_two, implemented = true, raw_input = false, params = true);
+    };
+    ($ident:ident, day = $day:literal, name = $name:expr, star_one = $star_one:ident, star_two = $star_two:ident, implemented = $implemented:literal, raw_input = $raw_input:literal, params = $params:tt) => {
+        pub struct $ident;
+
+        impl $crate::solution::Solution for $ident {
+            fn day (&self) -> u8 { $day }
+            fn name (&self) -> &'static str { $name }
+            fn star_one (&self, input: &str, params: &$crate::params::Params) -> $crate::error::AocResult<$crate::answer::Answer> {
+                $crate::solution::forward_star!($star_one, input, params, $params).map_err(| err | err.with_day($day))
+            }
+            fn star_two (&self, input: &str, params: &$crate::params::Params) -> $crate::error::AocResult<$crate::answer::Answer> {
+                $crate::solution::forward_star!($star_two, input, params, $params).map_err(| err | err.with_day($day))
+            }
+            fn implemented (&self) -> bool { $implemented }
+            fn raw_input (&self) -> bool { $raw_input }
+        }
+    };
+}
+pub(crate) use solution;
+
+// Calls a star function with or without the `--param`s, depending on whether its day takes any
+macro_rules! forward_star {
+    ($star:ident, $input:ident, $params:ident, true) => {
+        $star($input, $params)
+    };
+    ($star:ident, $input:ident, $params:ident, false) => {
+        $params.expect_keys(&[]).and_then(| _ | $star($input))
+    };
+}
+pub(crate) use forward_star;
+
+pub const DEFAULT_IMPL: &str = "default";
+
+// All implementations registered for `day`, with the default implementation first
+pub fn for_day (day: u8) -> Vec<&'static dyn Solution> {
+    let mut impls: Vec<&'static dyn Solution> = SOLUTIONS.iter()
+        .copied()
+        .filter(| solution | solution.day() == day)
+        .collect();
+    impls.sort_by_key(| solution | solution.name() != DEFAULT_IMPL);
+    impls
+}
+
+pub fn find (day: u8, name: &str) -> Option<&'static dyn Solution> {
+    SOLUTIONS.iter()
+        .copied()
+        .find(| solution | solution.day() == day && solution.name() == name)
+}
@@ -0,0 +1,135 @@
+// Polling file watcher for `--watch`
+//
+// Re-runs whenever a day's input files change, rebuilds with cargo when the crate's sources change (if we're
+//      running out of a checkout), and restarts itself when the binary on disk is replaced
+// Polling modification times is crude, but it needs no extra dependencies and a few files every half second is nothing
+use std::{collections::BTreeMap, env, fs, io, path::{Path, PathBuf}, process::Command, thread};
+use std::time::{Duration, SystemTime};
+use crate::alloc;
+use crate::log::{self, Level};
+
+// Modification time and size of every watched file, so both edits and truncations are picked up
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+pub struct WatchOpts {
+    // Every *.txt in here is watched, so new data files get noticed too
+    pub day_folder: PathBuf,
+    pub input_file: Option<PathBuf>,
+    pub poll_interval: Duration,
+}
+
+fn snapshot (files: &[PathBuf]) -> Snapshot {
+    files.iter()
+        .filter_map(| file | {
+            let metadata = fs::metadata(file).ok()?;
+            Some((file.clone(), (metadata.modified().ok(), metadata.len())))
+        })
+        .collect()
+}
+
+fn files_with_extension (dir: &Path, extension: &str, recursive: bool) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    entries.filter_map(Result::ok).fold(Vec::new(), | mut files, entry | {
+        let path = entry.path();
+        if path.is_dir() && recursive {
+            files.extend(files_with_extension(&path, extension, recursive));
+        }
+        else if path.extension().is_some_and(| ext | ext == extension) {
+            files.push(path);
+        }
+        files
+    })
+}
+
+fn input_files (opts: &WatchOpts) -> Vec<PathBuf> {
+    let mut files = files_with_extension(&opts.day_folder, "txt", false);
+    files.extend(opts.input_file.clone());
+    files
+}
+
+// Sources of the crate this binary was built from, if they're still around
+fn source_files () -> Vec<PathBuf> {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut files = files_with_extension(&manifest_dir.join("src"), "rs", true);
+    files.push(manifest_dir.join("Cargo.toml"));
+    files
+}
+
+// Same profile and features as the running binary, so the rebuild replaces it
+fn rebuild () -> io::Result<bool> {
+    let cargo = env::var_os("CARGO").unwrap_or("cargo".into());
+    let mut command = Command::new(cargo);
+    command.arg("build").current_dir(env!("CARGO_MANIFEST_DIR"));
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    if alloc::enabled() {
+        command.args([ "--features", "alloc-stats" ]);
+    }
+    Ok(command.status()?.success())
+}
+
+// Replaces this process with the (new) binary on disk, same arguments
+#[cfg(unix)]
+fn restart (binary: &Path) -> io::Error {
+    use std::os::unix::process::CommandExt;
+    Command::new(binary).args(env::args_os().skip(1)).exec()
+}
+
+// No exec outside of unix, so run the new binary as a child and exit with its status instead
+#[cfg(not(unix))]
+fn restart (binary: &Path) -> io::Error {
+    match Command::new(binary).args(env::args_os().skip(1)).status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(err) => err,
+    }
+}
+
+// Runs `run` once, then again every time an input file changes, forever
+pub fn watch (opts: &WatchOpts, mut run: impl FnMut()) -> ! {
+    let binary = env::current_exe().ok().into_iter().collect::<Vec<PathBuf>>();
+    let sources = source_files();
+
+    let mut input_snapshot = snapshot(&input_files(opts));
+    let mut source_snapshot = snapshot(&sources);
+    let mut binary_snapshot = snapshot(&binary);
+
+    run();
+    log::emit(Level::Info, format_args!("Watching {} for changes...", opts.day_folder.display()));
+
+    loop {
+        thread::sleep(opts.poll_interval);
+
+        let new_source_snapshot = snapshot(&sources);
+        if new_source_snapshot != source_snapshot {
+            source_snapshot = new_source_snapshot;
+            log::emit(Level::Info, format_args!("Sources changed, rebuilding..."));
+            match rebuild() {
+                Ok(true) => {},
+                Ok(false) => log::emit(Level::Warn, format_args!("Build failed, still running the old binary")),
+                Err(err) => log::emit(Level::Warn, format_args!("Could not run cargo: {}", err)),
+            }
+        }
+
+        // The binary can briefly disappear while cargo replaces it, so wait until it's back
+        let new_binary_snapshot = snapshot(&binary);
+        if new_binary_snapshot != binary_snapshot && new_binary_snapshot.len() == binary.len() {
+            binary_snapshot = new_binary_snapshot;
+            log::emit(Level::Info, format_args!("Binary changed, restarting..."));
+            if let Some(binary) = binary.first() {
+                let err = restart(binary);
+                log::emit(Level::Warn, format_args!("Could not restart: {}", err));
+            }
+        }
+
+        let new_input_snapshot = snapshot(&input_files(opts));
+        if new_input_snapshot != input_snapshot {
+            input_snapshot = new_input_snapshot;
+            log::emit(Level::Info, format_args!("Input changed, re-running..."));
+            run();
+        }
+    }
+}
@@ -0,0 +1,199 @@
+// Regression tests generated from the data folder
+//
+// Every day with an answers.toml (see `aoc_2025::answers`) gets one test case per data set, star, and implementation,
+//      so refactoring a day (or adding another implementation of it) gets checked against the known answers
+// The data folder defaults to ./data and can be moved with AOC_DATA_FOLDER, days without answers are skipped
+//
+// Runs without the libtest harness (`harness = false` in Cargo.toml) since the cases are only known at runtime
+// Arguments work like libtest's: `cargo test --test regression day_2::regex` only runs cases containing that
+//      string, and --skip, --exact, --list, --ignored, and --include-ignored do what they do there
+//
+// Alternative implementations can be slow on purpose (day 7's naive one is exponential), so on the full data
+//      set they're ignored unless asked for, and every case gives up after `CASE_TIMEOUT` either way
+
+use std::{env, path::PathBuf, process};
+use std::time::Duration;
+use aoc_2025::answers::Answers;
+use aoc_2025::params::Params;
+use aoc_2025::runner::{self, DataSet, Outcome};
+use aoc_2025::solution::{self, Part};
+
+const CASE_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Cases are only run once they've made it through the filters, listing them doesn't solve anything
+struct Case {
+    name: String,
+    ignored: bool,
+    run: Box<dyn Fn() -> Result<(), String>>,
+}
+
+// The libtest options that take a value, so the value isn't mistaken for a filter
+const OPTIONS_WITH_VALUES: [&str; 7] = [ "--skip", "--test-threads", "--format", "--logfile", "--color", "--shuffle-seed", "-Z" ];
+
+#[derive(Default)]
+struct Args {
+    filters: Vec<String>,
+    skip: Vec<String>,
+    exact: bool,
+    list: bool,
+    terse: bool,
+    ignored: bool,
+    include_ignored: bool,
+}
+
+impl Args {
+    // Anything else libtest knows about (--nocapture, --test-threads, ...) is accepted and ignored
+    fn parse (mut args: impl Iterator<Item = String>) -> Self {
+        let mut parsed = Args::default();
+        while let Some(arg) = args.next() {
+            let (option, inline_value) = match arg.split_once('=') {
+                Some((option, value)) if arg.starts_with("--") => (option.to_string(), Some(value.to_string())),
+                _ => (arg.clone(), None),
+            };
+            let mut value = || inline_value.clone().or_else(|| args.next()).unwrap_or_default();
+
+            match option.as_str() {
+                "--skip" => parsed.skip.push(value()),
+                "--format" => parsed.terse = value() == "terse",
+                "--exact" => parsed.exact = true,
+                "--list" => parsed.list = true,
+                "--ignored" => parsed.ignored = true,
+                "--include-ignored" => parsed.include_ignored = true,
+                option if OPTIONS_WITH_VALUES.contains(&option) => {
+                    if inline_value.is_none() {
+                        value();
+                    }
+                },
+                option if option.starts_with('-') => {},
+                _ => parsed.filters.push(arg),
+            }
+        }
+        parsed
+    }
+
+    fn matches (&self, pattern: &str, name: &str) -> bool {
+        if self.exact { name == pattern } else { name.contains(pattern) }
+    }
+
+    // --ignored leaves out every case that isn't ignored, like libtest does
+    fn selects (&self, case: &Case) -> bool {
+        (!self.ignored || case.ignored)
+            && (self.filters.is_empty() || self.filters.iter().any(| filter | self.matches(filter, &case.name)))
+            && !self.skip.iter().any(| skip | self.matches(skip, &case.name))
+    }
+
+    fn runs (&self, case: &Case) -> bool {
+        !case.ignored || self.ignored || self.include_ignored
+    }
+}
+
+fn data_folder_root () -> PathBuf {
+    match env::var_os("AOC_DATA_FOLDER") {
+        Some(folder) => PathBuf::from(folder),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data"),
+    }
+}
+
+fn collect_cases () -> Vec<Case> {
+    let data_folder_root = data_folder_root();
+    let mut cases: Vec<Case> = Vec::new();
+
+    for day in 1..=25 {
+        let answers = match Answers::load(&data_folder_root, day) {
+            Ok(answers) => answers,
+            Err(err) => {
+                cases.push(Case { name: format!("day_{}::answers", day), ignored: false, run: Box::new(move || Err(err.clone())) });
+                continue;
+            }
+        };
+
+        for solution in solution::for_day(day) {
+            for data_set in [ DataSet::Example, DataSet::Full ] {
+                for part in [ Part::One, Part::Two ] {
+                    let Some(expected) = answers.expected(data_set, part) else {
+                        continue;
+                    };
+
+                    let name = format!("day_{}::{}::star_{}::{}", day, solution.name(), part, data_set);
+                    let ignored = solution.name() != solution::DEFAULT_IMPL && data_set == DataSet::Full;
+                    let path = runner::data_path(&data_folder_root, day, data_set);
+                    let run = move || match runner::read_input(&path) {
+                        Err(err) => Err(format!("could not read '{}': {}", path.display(), err)),
+                        Ok(input) => match runner::run_with_timeout(solution, part, &input, &Params::default().for_data_set(Some(data_set)), Some(CASE_TIMEOUT)).0 {
+                            Outcome::Solved(actual) if actual == expected => Ok(()),
+                            Outcome::Solved(actual) => Err(format!("expected {}, got {}", expected, actual)),
+                            other => Err(format!("expected {}, but {}", expected, other)),
+                        }
+                    };
+                    cases.push(Case { name, ignored, run: Box::new(run) });
+                }
+            }
+        }
+    }
+    cases
+}
+
+fn main () {
+    let args = Args::parse(env::args().skip(1));
+    let mut cases = collect_cases();
+    let total = cases.len();
+    cases.retain(| case | args.selects(case));
+
+    if args.list {
+        for case in &cases {
+            println!("{}: test", case.name);
+        }
+        if !args.terse {
+            println!();
+            println!("{} tests, 0 benchmarks", cases.len());
+        }
+        return;
+    }
+
+    println!();
+    println!("running {} tests", cases.len());
+
+    // Panics are reported as the case's failure instead of going to stderr
+    let quiet = runner::quiet_panics();
+    let mut failures: Vec<(&Case, String)> = Vec::new();
+    let mut ignored = 0;
+    for case in &cases {
+        if !args.runs(case) {
+            println!("test {} ... ignored", case.name);
+            ignored += 1;
+            continue;
+        }
+
+        match (case.run)() {
+            Ok(()) => println!("test {} ... ok", case.name),
+            Err(err) => {
+                println!("test {} ... FAILED", case.name);
+                failures.push((case, err));
+            }
+        }
+    }
+    drop(quiet);
+
+    if !failures.is_empty() {
+        println!();
+        println!("failures:");
+        for (case, err) in &failures {
+            println!("    {}: {}", case.name, err);
+        }
+    }
+
+    println!();
+    println!(
+        "test result: {}. {} passed; {} failed; {} ignored; {} filtered out",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        cases.len() - failures.len() - ignored,
+        failures.len(),
+        ignored,
+        total - cases.len()
+    );
+    println!();
+
+    if !failures.is_empty() {
+        process::exit(1);
+    }
+}